@@ -0,0 +1,132 @@
+use std::{env, fs, path::Path};
+
+/// Splits a file of `<key> <part> <answer>` lines into its entries, skipping
+/// blank lines and lines starting with `#`.
+fn entries<'a>(text: &'a str, file: &str) -> Vec<(&'a str, usize, &'a str)> {
+    let mut entries = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut fields = line.splitn(3, ' ');
+        let (Some(key), Some(part), Some(answer)) = (fields.next(), fields.next(), fields.next())
+        else {
+            panic!("invalid line in {}: '{}'", file, line);
+        };
+        let part = part
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid part in {}: '{}'", file, line));
+        entries.push((key, part, answer.trim()));
+    }
+    entries
+}
+
+fn test(
+    name: &str,
+    day: u32,
+    path: &str,
+    part: usize,
+    answer: &str,
+    params: &[(&str, i64)],
+) -> String {
+    format!(
+        "#[test]\nfn {}() {{\n    check({}, {:?}, {}, {:?}, &{:?});\n}}\n\n",
+        name, day, path, part, answer, params
+    )
+}
+
+/// Splits the key of an example's answer, such as `ex1,steps=6`, into the
+/// example and the parameters to solve it with.
+fn example_key<'a>(key: &'a str, file: &str) -> (&'a str, Vec<(&'a str, i64)>) {
+    let mut fields = key.split(',');
+    let example = fields.next().unwrap();
+    let params = fields
+        .map(|param| {
+            param
+                .split_once('=')
+                .and_then(|(name, value)| Some((name, value.parse().ok()?)))
+                .unwrap_or_else(|| panic!("invalid parameter in {}: '{}'", file, param))
+        })
+        .collect();
+    (example, params)
+}
+
+/// Generates one test per entry of the `answers` file.
+fn answers() -> String {
+    println!("cargo:rerun-if-changed=answers");
+
+    let answers = fs::read_to_string("answers").unwrap_or_default();
+    let mut tests = String::new();
+    for (day, part, answer) in entries(&answers, "answers") {
+        let day: u32 = day.parse().expect("invalid day in answers");
+        let name = format!("day{:02}p{}", day, part);
+        let path = format!("input/day{:02}", day);
+        tests.push_str(&test(&name, day, &path, part, answer, &[]));
+    }
+    tests
+}
+
+/// Generates one test per part of each example in `examples/dayNN/*.txt`,
+/// with the expected answers given in `examples/dayNN/answers`. An answer
+/// may be for different values of the day's parameters than the defaults,
+/// given after the example as in `ex1,steps=6`. Empty examples, as set up by
+/// `aoc2023 new`, are skipped until they are filled in.
+fn examples() -> String {
+    println!("cargo:rerun-if-changed=examples");
+
+    let mut tests = String::new();
+    for day in 1..=25 {
+        let dir = format!("examples/day{:02}", day);
+        let Ok(files) = fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut names: Vec<String> = files
+            .map(|file| file.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+            .filter(|path| fs::metadata(path).is_ok_and(|meta| meta.len() > 0))
+            .map(|path| path.file_stem().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+
+        let file = format!("{}/answers", dir);
+        let answers = fs::read_to_string(&file).unwrap_or_default();
+        let entries: Vec<_> = entries(&answers, &file)
+            .into_iter()
+            .map(|(key, part, answer)| (example_key(key, &file), part, answer))
+            .collect();
+        for ((example, _), _, _) in &entries {
+            assert!(
+                names.iter().any(|name| name == example),
+                "no such example in {}: '{}'",
+                file,
+                example
+            );
+        }
+
+        for name in names {
+            let path = format!("{}/{}.txt", dir, name);
+            let parts: Vec<_> = entries
+                .iter()
+                .filter(|((example, _), _, _)| *example == name)
+                .collect();
+            assert!(!parts.is_empty(), "no answers for {}", path);
+            for ((_, params), part, answer) in parts {
+                let mut test_name = format!("day{:02}_{}", day, name);
+                for (param, value) in params {
+                    test_name += &format!("_{}_{}", param, value).replace('-', "m");
+                }
+                test_name += &format!("_p{}", part);
+                tests.push_str(&test(&test_name, day, &path, *part, answer, params));
+            }
+        }
+    }
+    tests
+}
+
+fn main() {
+    let out = env::var("OUT_DIR").unwrap();
+    let out = Path::new(&out);
+    fs::write(out.join("answers.rs"), answers()).unwrap();
+    fs::write(out.join("examples.rs"), examples()).unwrap();
+}
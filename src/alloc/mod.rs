@@ -0,0 +1,174 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+};
+
+/// Allocator that counts what each thread allocates, for the binary to
+/// install as its global allocator with the `count-alloc` feature
+pub struct Counting;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counters {
+    count: usize,
+    bytes: usize,
+    /// Bytes allocated and not yet freed. Memory can be freed by another
+    /// thread than the one that allocated it, so this may go negative.
+    live: isize,
+    peak: isize,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            count: 0,
+            bytes: 0,
+            live: 0,
+            peak: 0,
+        })
+    };
+}
+
+/// Records an allocation of `size` bytes that replaces `freed` bytes.
+fn count(size: usize, freed: usize) {
+    // the thread local is gone while the thread shuts down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+        c.count += 1;
+        c.bytes += size;
+        c.live += size as isize - freed as isize;
+        c.peak = c.peak.max(c.live);
+        counters.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            count(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            count(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            count(new_size, layout.size());
+        }
+        new
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            c.live -= layout.size() as isize;
+            counters.set(c);
+        });
+    }
+}
+
+/// What a thread allocated while doing something
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// Number of allocations, counting reallocations
+    pub count: usize,
+    /// Total bytes allocated
+    pub bytes: usize,
+    /// Most bytes in use at once, beyond what was in use before
+    pub peak: usize,
+}
+
+/// Calls `f` and counts what the current thread allocates while it runs.
+///
+/// Everything counts as zero unless [`Counting`] is the global allocator.
+pub fn measure<F: FnOnce() -> R, R>(f: F) -> (R, Stats) {
+    let mut start = COUNTERS.get();
+    start.peak = start.live;
+    COUNTERS.set(start);
+    let res = f();
+    let end = COUNTERS.get();
+    let stats = Stats {
+        count: end.count - start.count,
+        bytes: end.bytes - start.bytes,
+        peak: (end.peak - start.live).max(0) as usize,
+    };
+    (res, stats)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.count,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measure() {
+        // the tests don't use the counting allocator, so count by hand
+        let (_, stats) = super::measure(|| {
+            count(100, 0);
+            count(300, 100);
+            COUNTERS.with(|counters| {
+                let mut c = counters.get();
+                c.live -= 300;
+                counters.set(c);
+            });
+            count(50, 0);
+        });
+        assert_eq!(
+            stats,
+            Stats {
+                count: 3,
+                bytes: 450,
+                peak: 300
+            }
+        );
+    }
+
+    #[test]
+    fn display() {
+        let stats = Stats {
+            count: 12,
+            bytes: 3 * 1024 * 1024 / 2,
+            peak: 512,
+        };
+        assert_eq!(
+            stats.to_string(),
+            "12 allocs, 1.5 MiB allocated, 512 B peak"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// The answer to a part.
+///
+/// Each answer has a single representation, so that answers can be compared
+/// with `==` whatever they were computed or read from: strings that are the
+/// decimal form of an integer, such as `"42"` but not `"007"`, are integers,
+/// integers are only [`Answer::BigInt`] if they don't fit in an `i64`, and
+/// text is only [`Answer::Text`] if it has more than one line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    /// An integer too large for an `i64`
+    BigInt(i128),
+    /// A single line of text
+    Str(String),
+    /// Several lines of text, such as a grid
+    Text(String),
+}
+
+macro_rules! from_int {
+    ($($t:ty),+) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Int(n),
+                    Err(_) => Answer::BigInt(n as i128),
+                }
+            }
+        })+
+    };
+}
+
+from_int!(i32, u32, i64, u64, usize, i128);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if let Some(n) = s.parse::<i128>().ok().filter(|n| n.to_string() == s) {
+            Answer::from(n)
+        } else if s.contains('\n') {
+            Answer::Text(s)
+        } else {
+            Answer::Str(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_string())
+    }
+}
+
+impl Answer {
+    /// Whether the answer is an integer, for output formats that tell numbers
+    /// and strings apart
+    pub fn is_int(&self) -> bool {
+        matches!(self, Answer::Int(_) | Answer::BigInt(_))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Str(s) | Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Reads an answer written on a single line, as in the answers files, where
+/// the lines of [`Answer::Text`] are separated by `\n` escapes.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Infallible> {
+        Ok(Answer::from(s.replace("\\n", "\n")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        assert_eq!(Answer::from(42u32), Answer::from(42i64));
+        assert_eq!(Answer::from(42usize), "42".parse().unwrap());
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as i128));
+        assert_eq!(
+            Answer::from(u64::MAX),
+            u64::MAX.to_string().parse().unwrap()
+        );
+        assert_eq!(Answer::from("ab"), Answer::Str("ab".to_string()));
+        assert_eq!(Answer::from("#.\n.#"), "#.\\n.#".parse().unwrap());
+        assert_ne!(Answer::from(42), Answer::from("042"));
+
+        for s in [
+            "42",
+            "-7",
+            "0",
+            "007",
+            "+3",
+            "-0",
+            "170141183460469231731687303715884105728",
+        ] {
+            assert_eq!(Answer::from(s.to_string()), s.parse().unwrap(), "{}", s);
+        }
+        assert_eq!(Answer::from("42"), Answer::Int(42));
+        assert_eq!(Answer::from("007"), Answer::Str("007".to_string()));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::Answer;
+use std::{collections::HashMap, fmt, str::FromStr};
+
+/// Expected answers by day and part, read from a file of
+/// `<day> <part> <answer>` lines. Blank lines and lines starting with `#`
+/// are ignored.
+#[derive(Debug, Default)]
+pub struct Answers(HashMap<(u32, usize), Answer>);
+
+impl Answers {
+    pub fn get(&self, day: u32, part: usize) -> Option<&Answer> {
+        self.0.get(&(day, part))
+    }
+}
+
+impl FromStr for Answers {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let mut answers = HashMap::new();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, ' ');
+            let (Some(day), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(crate::Error::parse(line, "'<day> <part> <answer>'"));
+            };
+            let day = crate::error::parse(day)?;
+            let part = crate::error::parse(part)?;
+            let Ok(answer) = answer.trim().parse();
+            answers.insert((day, part), answer);
+        }
+        Ok(Answers(answers))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Unknown,
+}
+
+impl Status {
+    pub fn new(answer: &Result<Answer, String>, expected: Option<&Answer>) -> Self {
+        match (answer, expected) {
+            (Err(_), _) => Status::Fail,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Pass => "PASS",
+            Status::Fail => "FAIL",
+            Status::Unknown => "UNKNOWN",
+        };
+        f.pad(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let answers: Answers = "# comment\n\n1 1 54953\n 25 1 602151 \n".parse().unwrap();
+        assert_eq!(answers.get(1, 1), Some(&Answer::Int(54953)));
+        assert_eq!(answers.get(25, 1), Some(&Answer::Int(602151)));
+        assert_eq!(answers.get(25, 2), None);
+
+        assert!("1 1".parse::<Answers>().is_err());
+        assert!("one 1 54953".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn status() {
+        let ok = |n: i64| Ok(Answer::from(n));
+        let expected = Answer::from(42);
+        assert_eq!(Status::new(&ok(42), Some(&expected)), Status::Pass);
+        assert_eq!(Status::new(&ok(43), Some(&expected)), Status::Fail);
+        assert_eq!(Status::new(&ok(42), None), Status::Unknown);
+        assert_eq!(
+            Status::new(&Err("bad".to_string()), Some(&expected)),
+            Status::Fail
+        );
+    }
+}
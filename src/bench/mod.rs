@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use crate::report::format_time;
+
+/// Summary of the timings of repeated runs of a part
+#[derive(Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let runs = samples.len();
+
+        let median = if runs.is_multiple_of(2) {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        } else {
+            samples[runs / 2]
+        };
+
+        // nearest-rank percentile
+        let p95 = samples[(runs * 95).div_ceil(100) - 1];
+
+        let mean = samples.iter().sum::<Duration>() / runs as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / runs as f64;
+
+        Some(Stats {
+            runs,
+            min: samples[0],
+            median,
+            mean,
+            p95,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+pub fn header() -> String {
+    format!(
+        "{:>3}  {:>5}  {:>6}  {:<13}  {:<13}  {:<13}  {:<13}  StdDev",
+        "Day", "Part", "Runs", "Min", "Median", "Mean", "P95"
+    )
+}
+
+/// Formats the statistics of a day's part, or its parsing, as labelled by
+/// `label`.
+pub fn row(day: u32, label: &str, stats: &Stats) -> String {
+    format!(
+        "{:>3}  {:>5}  {:>6}  {}  {}  {}  {}  {}",
+        day,
+        label,
+        stats.runs,
+        format_time(stats.min),
+        format_time(stats.median),
+        format_time(stats.mean),
+        format_time(stats.p95),
+        format_time(stats.stddev),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        assert_eq!(Stats::new(&[]), None);
+
+        let ms = Duration::from_millis;
+        let samples: Vec<_> = [5, 1, 4, 2, 3].into_iter().map(ms).collect();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, ms(3));
+        assert_eq!(stats.p95, ms(5));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+
+        let samples: Vec<_> = (1..=20).map(ms).collect();
+        let stats = Stats::new(&samples).unwrap();
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.p95, ms(19));
+    }
+}
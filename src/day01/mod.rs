@@ -1,8 +1,3 @@
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 fn digit_p1(cand: &str) -> Option<u32> {
     cand.chars().next().and_then(|c| c.to_digit(10))
 }
@@ -28,29 +23,37 @@ fn digit_p2(cand: &str) -> Option<u32> {
 }
 
 fn calibration_value(line: &str, digit: fn(&str) -> Option<u32>) -> crate::Result<u32> {
-    let first = (0..line.len())
-        .find_map(|needle| digit(&line[needle..]))
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-    let last = (0..line.len())
+    let first = line
+        .char_indices()
+        .find_map(|(needle, _)| digit(&line[needle..]))
+        .ok_or_else(|| crate::Error::parse(line, "a digit"))?;
+    let last = line
+        .char_indices()
         .rev()
-        .find_map(|needle| digit(&line[needle..]))
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+        .find_map(|(needle, _)| digit(&line[needle..]))
+        .ok_or_else(|| crate::Error::parse(line, "a digit"))?;
     Ok(first * 10 + last)
 }
 
-pub fn sum(input: &str, digit: fn(&str) -> Option<u32>) -> crate::Result<u32> {
-    Ok(input
-        .lines()
+pub fn sum(lines: &[&str], digit: fn(&str) -> Option<u32>) -> crate::Result<u32> {
+    Ok(lines
+        .iter()
         .map(|line| calibration_value(line, digit))
-        .collect::<Result<Vec<_>, _>>()?
+        .collect::<crate::Result<Vec<_>>>()?
         .iter()
         .sum())
 }
 
-pub fn part1(input: &str) -> crate::Result<u32> {
-    sum(input, digit_p1)
+pub fn parse(input: &str) -> crate::Result<Vec<&str>> {
+    Ok(input.lines().collect())
 }
 
-pub fn part2(input: &str) -> crate::Result<u32> {
-    sum(input, digit_p2)
+pub fn part1(lines: &[&str]) -> crate::Result<u32> {
+    sum(lines, digit_p1)
 }
+
+pub fn part2(lines: &[&str]) -> crate::Result<u32> {
+    sum(lines, digit_p2)
+}
+
+pub const SOLUTION: crate::Solution = solution!(1, "Trebuchet?!", parse, [part1, part2]);
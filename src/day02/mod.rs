@@ -1,11 +1,6 @@
 use std::cmp::max;
 use std::str::FromStr;
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 #[derive(Debug)]
 struct CubeCount {
     r: u32,
@@ -24,32 +19,31 @@ impl CubeCount {
 }
 
 impl FromStr for CubeCount {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
-        let [mut r, mut g, mut b] = [0, 0, 0];
+        let [mut r, mut g, mut b]: [u32; 3] = [0, 0, 0];
         for color in s.trim().split(", ") {
-            let mut parts = color.split(' ');
-            let cnt = parts
-                .next()
-                .ok_or(crate::Error::boxed(Error::InvalidInput))?
-                .parse::<u32>()?;
-            match parts
-                .next()
-                .ok_or(crate::Error::boxed(Error::InvalidInput))?
-            {
-                "red" => r += cnt,
-                "green" => g += cnt,
-                "blue" => b += cnt,
-                _ => return Err(crate::Error::boxed(Error::InvalidInput)),
-            }
+            let (cnt, name) = color
+                .split_once(' ')
+                .ok_or_else(|| crate::Error::parse(color, "'<count> <color>'"))?;
+            let cnt = crate::error::parse::<u32>(cnt)?;
+            let total = match name {
+                "red" => &mut r,
+                "green" => &mut g,
+                "blue" => &mut b,
+                _ => return Err(crate::Error::parse(name, "red, green or blue")),
+            };
+            *total = total
+                .checked_add(cnt)
+                .ok_or_else(|| crate::Error::parse(color, "fewer cubes"))?;
         }
         Ok(CubeCount { r, g, b })
     }
 }
 
 #[derive(Debug)]
-struct Game {
+pub struct Game {
     id: u32,
     rounds: Vec<CubeCount>,
 }
@@ -61,61 +55,59 @@ impl Game {
             .all(|cc| cc.r <= cube_count.r && cc.g <= cube_count.g && cc.b <= cube_count.b)
     }
 
-    fn power(&self) -> u32 {
+    fn power(&self) -> Option<u32> {
         let cnt = self
             .rounds
             .iter()
             .fold(CubeCount { r: 0, g: 0, b: 0 }, CubeCount::max);
-        cnt.r * cnt.g * cnt.b
+        cnt.r.checked_mul(cnt.g)?.checked_mul(cnt.b)
     }
 }
 
 impl FromStr for Game {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
-        let mut parts = s.split(':');
-        let id = parts
-            .next()
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
-            .trim_matches(|c: char| !c.is_numeric())
-            .parse::<u32>()?;
-        let rounds = parts
-            .next()
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
+        let (id, rounds) = s
+            .split_once(':')
+            .ok_or_else(|| crate::Error::parse(s, "'Game <id>: <rounds>'"))?;
+        let id = crate::error::parse::<u32>(id.trim_matches(|c: char| !c.is_numeric()))?;
+        let rounds = rounds
             .split(';')
             .map(CubeCount::from_str)
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<crate::Result<Vec<_>>>()?;
         Ok(Game { id, rounds })
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<u32> {
+pub fn parse(input: &str) -> crate::Result<Vec<Game>> {
+    input.lines().map(Game::from_str).collect()
+}
+
+pub fn part1(games: &[Game], params: &crate::Params) -> crate::Result<u32> {
     let cube_count = CubeCount {
-        r: 12,
-        g: 13,
-        b: 14,
+        r: params.get("red")?,
+        g: params.get("green")?,
+        b: params.get("blue")?,
     };
-    let games = input
-        .lines()
-        .map(Game::from_str)
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(games
+    games
         .iter()
-        .filter_map(|g| {
-            if g.is_possible(&cube_count) {
-                Some(g.id)
-            } else {
-                None
-            }
-        })
-        .sum())
+        .filter(|g| g.is_possible(&cube_count))
+        .try_fold(0u32, |sum, g| sum.checked_add(g.id))
+        .ok_or_else(|| crate::Error::invalid("the sum of the game IDs overflows"))
 }
 
-pub fn part2(input: &str) -> crate::Result<u32> {
-    let games = input
-        .lines()
-        .map(Game::from_str)
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(games.iter().map(Game::power).sum())
+pub fn part2(games: &[Game]) -> crate::Result<u32> {
+    games
+        .iter()
+        .try_fold(0u32, |sum, g| sum.checked_add(g.power()?))
+        .ok_or_else(|| crate::Error::invalid("the sum of the powers overflows"))
 }
+
+pub const SOLUTION: crate::Solution = solution!(
+    2,
+    "Cube Conundrum",
+    parse,
+    [part1(params), part2],
+    params[red = 12, green = 13, blue = 14]
+);
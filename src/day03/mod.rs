@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
-struct Number {
+pub struct Number {
     num: i64,
     pos: (i64, i64),
     len: i64,
@@ -36,67 +36,65 @@ impl Number {
     }
 }
 
-fn parse(input: &str) -> (HashMap<(i64, i64), char>, Vec<Number>) {
+type Schematic = (HashMap<(i64, i64), char>, Vec<Number>);
+
+pub fn parse(input: &str) -> crate::Result<Schematic> {
     let mut map = HashMap::new();
     let mut numbers = Vec::new();
     for (y, line) in input.lines().enumerate() {
         let mut x = 0;
-        let mut it = line.chars().peekable();
-        while let Some(c) = it.next() {
+        let mut it = line.char_indices().peekable();
+        while let Some((start, c)) = it.next() {
             map.insert((x, y as i64), c);
 
             if c.is_ascii_digit() {
-                let mut num = c.to_string();
                 let pos = (x, y as i64);
                 let mut len = 1;
-                while let Some(true) = it.peek().map(char::is_ascii_digit) {
+                while let Some(true) = it.peek().map(|(_, c)| c.is_ascii_digit()) {
                     x += 1;
-                    let c = it.next().unwrap();
+                    let (_, c) = it.next().unwrap();
                     map.insert((x, y as i64), c);
-                    num.push(c);
                     len += 1;
                 }
-                let num = num.parse().unwrap();
+                // digits are ASCII, so the number is `len` bytes long
+                let num = crate::error::parse(&line[start..start + len as usize])?;
                 numbers.push(Number { num, pos, len });
             }
 
             x += 1;
         }
     }
-    (map, numbers)
+    Ok((map, numbers))
 }
-pub fn part1(input: &str) -> crate::Result<i64> {
-    let (map, numbers) = parse(input);
-    let sum = numbers
-        .into_iter()
-        .filter_map(|n| {
-            if n.is_part_number(&map) {
-                Some(n.num)
-            } else {
-                None
-            }
-        })
-        .sum();
-    Ok(sum)
+
+fn overflow() -> crate::Error {
+    crate::Error::invalid("the sum overflows")
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    let (map, numbers) = parse(input);
+pub fn part1(schematic: &Schematic) -> crate::Result<i64> {
+    let (map, numbers) = schematic;
+    numbers
+        .iter()
+        .filter(|n| n.is_part_number(map))
+        .try_fold(0i64, |sum, n| sum.checked_add(n.num))
+        .ok_or_else(overflow)
+}
+
+pub fn part2(schematic: &Schematic) -> crate::Result<i64> {
+    let (map, numbers) = schematic;
     let mut numbers_by_gear: HashMap<(i64, i64), Vec<Number>> = HashMap::new();
     for num in numbers {
-        for pos in num.gear_pos(&map) {
+        for pos in num.gear_pos(map) {
             numbers_by_gear.entry(pos).or_default().push(num.clone());
         }
     }
-    let sum = numbers_by_gear
-        .into_iter()
-        .filter_map(|(_, nums)| {
-            if nums.len() == 2 {
-                Some(nums[0].num * nums[1].num)
-            } else {
-                None
-            }
+    numbers_by_gear
+        .into_values()
+        .filter(|nums| nums.len() == 2)
+        .try_fold(0i64, |sum, nums| {
+            sum.checked_add(nums[0].num.checked_mul(nums[1].num)?)
         })
-        .sum();
-    Ok(sum)
+        .ok_or_else(overflow)
 }
+
+pub const SOLUTION: crate::Solution = solution!(3, "Gear Ratios", parse, [part1, part2]);
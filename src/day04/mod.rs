@@ -1,30 +1,28 @@
 use std::str::FromStr;
 
 #[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
-#[derive(Debug)]
-struct Card {
+pub struct Card {
     winners: Vec<u32>,
     numbers: Vec<u32>,
-    count: u32,
+}
+
+fn overflow() -> crate::Error {
+    crate::Error::invalid("the points overflow")
 }
 
 impl Card {
-    fn score(self) -> u32 {
-        let mut score = 0;
+    fn score(&self) -> crate::Result<u32> {
+        let mut score: u32 = 0;
         for num in &self.numbers {
             if self.winners.contains(num) {
                 if score == 0 {
                     score = 1;
                 } else {
-                    score *= 2;
+                    score = score.checked_mul(2).ok_or_else(overflow)?;
                 }
             }
         }
-        score
+        Ok(score)
     }
 
     fn score_p2(&self) -> usize {
@@ -36,15 +34,15 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
         let parts = s
             .split_once(':')
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
+            .ok_or_else(|| crate::Error::parse(s, "'Card <id>: <numbers>'"))?
             .1
             .split_once('|')
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+            .ok_or_else(|| crate::Error::parse(s, "'<winners> | <numbers>'"))?;
         let winners: Vec<u32> = parts
             .0
             .split_whitespace()
@@ -57,35 +55,35 @@ impl FromStr for Card {
             .map(str::trim)
             .filter_map(|s| s.parse().ok())
             .collect();
-        let count = 1;
-        Ok(Card {
-            winners,
-            numbers,
-            count,
-        })
+        Ok(Card { winners, numbers })
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<u32> {
-    let mut sum = 0;
-    for line in input.lines() {
-        sum += line.parse::<Card>()?.score();
-    }
-    Ok(sum)
+pub fn parse(input: &str) -> crate::Result<Vec<Card>> {
+    input.lines().map(Card::from_str).collect()
+}
+
+pub fn part1(cards: &[Card]) -> crate::Result<u32> {
+    cards.iter().try_fold(0u32, |sum, card| {
+        sum.checked_add(card.score()?).ok_or_else(overflow)
+    })
 }
 
-pub fn part2(input: &str) -> crate::Result<u32> {
-    let mut cards = input
-        .lines()
-        .map(Card::from_str)
-        .collect::<Result<Vec<_>, _>>()?;
+pub fn part2(cards: &[Card]) -> crate::Result<u32> {
+    let mut counts: Vec<u32> = vec![1; cards.len()];
     for index in 0..cards.len() {
         for i in index + 1..=index + cards[index].score_p2() {
-            cards
+            let won = counts[index];
+            let count = counts
                 .get_mut(i)
-                .ok_or(crate::Error::boxed(Error::InvalidInput))?
-                .count += cards[index].count;
+                .ok_or_else(|| crate::Error::invalid("cards won past the end of the table"))?;
+            *count = count.checked_add(won).ok_or_else(overflow)?;
         }
     }
-    Ok(cards.into_iter().map(|c| c.count).sum())
+    counts
+        .into_iter()
+        .try_fold(0u32, |sum, count| sum.checked_add(count))
+        .ok_or_else(overflow)
 }
+
+pub const SOLUTION: crate::Solution = solution!(4, "Scratchcards", parse, [part1, part2]);
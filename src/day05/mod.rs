@@ -1,10 +1,5 @@
 use std::{cmp::min, str::FromStr};
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 #[derive(Debug, Clone, PartialEq)]
 struct Range {
     start: i64,
@@ -48,6 +43,12 @@ impl Range {
     }
 }
 
+/// Parses a number of the almanac, which is a `u32`, so that adding up a few
+/// of them can't overflow an `i64`.
+fn number(s: &str) -> crate::Result<i64> {
+    crate::error::parse::<u32>(s).map(i64::from)
+}
+
 #[derive(Debug, Clone)]
 struct Mapping {
     dst_start: i64,
@@ -76,19 +77,21 @@ impl Mapping {
 }
 
 impl FromStr for Mapping {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
-        if let &[Ok(dst_start), Ok(src_start), Ok(length)] =
-            &s.split_whitespace().map(str::parse).collect::<Vec<_>>()[..3]
-        {
+        let numbers = s
+            .split_whitespace()
+            .map(number)
+            .collect::<crate::Result<Vec<i64>>>()?;
+        if let &[dst_start, src_start, length] = &numbers[..] {
             Ok(Mapping {
                 dst_start,
                 src_start,
                 length,
             })
         } else {
-            Err(crate::Error::boxed(Error::InvalidInput))
+            Err(crate::Error::parse(s, "'<dst> <src> <length>'"))
         }
     }
 }
@@ -121,33 +124,45 @@ impl Table {
 }
 
 impl FromStr for Table {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
         let mappings = s
             .lines()
             .skip(1)
             .map(Mapping::from_str)
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<crate::Result<Vec<_>>>()?;
         Ok(Table { mappings })
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<i64> {
-    let mut parts = input.split("\n\n");
+#[derive(Debug)]
+pub struct Almanac {
+    seeds: Vec<i64>,
+    tables: Vec<Table>,
+}
+
+pub fn parse(input: &str) -> crate::Result<Almanac> {
+    let mut parts = crate::input::sections(input);
     let seeds = parts
         .next()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?
+        .ok_or_else(|| crate::Error::parse(input, "seeds"))?
         .trim_start_matches("seeds: ")
         .split_whitespace()
-        .map(str::parse)
-        .collect::<Result<Vec<_>, _>>()?;
-    let tables = parts.map(Table::from_str).collect::<Result<Vec<_>, _>>()?;
+        .map(number)
+        .collect::<crate::Result<Vec<_>>>()?;
+    let tables = parts
+        .map(Table::from_str)
+        .collect::<crate::Result<Vec<_>>>()?;
+    Ok(Almanac { seeds, tables })
+}
 
-    let min = seeds
-        .into_iter()
-        .map(|mut src| {
-            for table in &tables {
+pub fn part1(almanac: &Almanac) -> crate::Result<i64> {
+    let min = almanac
+        .seeds
+        .iter()
+        .map(|&(mut src)| {
+            for table in &almanac.tables {
                 src = table
                     .mappings
                     .iter()
@@ -157,39 +172,34 @@ pub fn part1(input: &str) -> crate::Result<i64> {
             src
         })
         .min()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+        .ok_or_else(|| crate::Error::invalid("no seeds"))?;
     Ok(min)
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    let mut parts = input.split("\n\n");
-    let seeds = parts
-        .next()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?
-        .trim_start_matches("seeds: ")
-        .split_whitespace()
-        .map(str::parse::<i64>)
-        .collect::<Result<Vec<_>, _>>()?;
-    let mut seeds: Vec<Range> = seeds
+pub fn part2(almanac: &Almanac) -> crate::Result<i64> {
+    let mut seeds: Vec<Range> = almanac
+        .seeds
         .chunks_exact(2)
         .map(|chunk| Range::new(chunk[0], chunk[1]))
         .collect();
-    let tables = parts.map(Table::from_str).collect::<Result<Vec<_>, _>>()?;
 
-    for table in &tables {
+    for table in &almanac.tables {
         let mut tmp = Vec::new();
         for seed in &seeds {
             tmp.extend(table.map_range(seed));
         }
         seeds = tmp;
     }
-    Ok(seeds
+    seeds
         .into_iter()
         .map(|s| s.start)
         .min()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?)
+        .ok_or_else(|| crate::Error::invalid("no seeds"))
 }
 
+pub const SOLUTION: crate::Solution =
+    solution!(5, "If You Give A Seed A Fertilizer", parse, [part1, part2]);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,42 +238,4 @@ mod tests {
         assert_eq!(Range::new(8, 2), unmatched[0]);
         assert_eq!(Range::new(20, 3), unmatched[1]);
     }
-
-    #[test]
-    fn ex1() {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        assert_eq!(46, part2(input).unwrap());
-    }
 }
@@ -1,59 +1,74 @@
+/// The time and distance rows, read both as separate races and as a single
+/// race with bad kerning
 #[derive(Debug)]
-enum Error {
-    InvalidInput,
+pub struct Races {
+    races: Vec<(u64, u64)>,
+    race: (u64, u64),
 }
 
-pub fn part1(input: &str) -> crate::Result<u32> {
+pub fn parse(input: &str) -> crate::Result<Races> {
     let mut lines = input.lines();
-    let times = lines
+    let times: Vec<&str> = lines
         .next()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?
+        .ok_or_else(|| crate::Error::parse(input, "a line of times"))?
         .split_whitespace()
-        .skip(1);
-    let dists = lines
+        .skip(1)
+        .collect();
+    let dists_line = lines
         .next()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?
-        .split_whitespace()
-        .skip(1);
+        .ok_or_else(|| crate::Error::parse(input, "a line of distances"))?;
+    let dists: Vec<&str> = dists_line.split_whitespace().skip(1).collect();
+    if dists.len() != times.len() {
+        return Err(crate::Error::parse(dists_line, "a distance per time"));
+    }
 
-    let mut res = 1;
-    for (time, dist) in times.zip(dists) {
-        let time: u32 = time.parse()?;
-        let dist: u32 = dist.parse()?;
+    let races = times
+        .iter()
+        .zip(&dists)
+        .map(|(time, dist)| Ok((crate::error::parse(time)?, crate::error::parse(dist)?)))
+        .collect::<crate::Result<_>>()?;
+    let race = (
+        crate::error::parse(&times.concat())?,
+        crate::error::parse(&dists.concat())?,
+    );
+    Ok(Races { races, race })
+}
 
-        let mut cnt = 0;
-        for cand in 1..time {
-            if cand * (time - cand) > dist {
-                cnt += 1;
-            }
+/// Number of ways to beat `dist` in a race lasting `time`, holding the button
+/// for a whole number of milliseconds.
+fn ways(time: u64, dist: u64) -> u64 {
+    // widened, as the product of two u64 can't overflow a u128
+    let beats = |hold: u64| hold as u128 * (time - hold) as u128 > dist as u128;
+    // the distance grows with the hold up to half the time, and then shrinks
+    // the same way
+    let half = time / 2;
+    if !beats(half) {
+        return 0;
+    }
+    // the shortest hold that beats the distance, which holding for 0 doesn't
+    let (mut lo, mut hi) = (0, half);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if beats(mid) {
+            hi = mid;
+        } else {
+            lo = mid;
         }
-        res *= cnt;
     }
-    Ok(res)
+    time - 2 * hi + 1
 }
 
-pub fn part2(input: &str) -> crate::Result<u32> {
-    let mut lines = input.lines();
-    let time: u64 = lines
-        .next()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?
-        .split_whitespace()
-        .skip(1)
-        .collect::<String>()
-        .parse()?;
-    let dist: u64 = lines
-        .next()
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?
-        .split_whitespace()
-        .skip(1)
-        .collect::<String>()
-        .parse()?;
+pub fn part1(races: &Races) -> crate::Result<u64> {
+    races
+        .races
+        .iter()
+        .try_fold(1u64, |res, &(time, dist)| res.checked_mul(ways(time, dist)))
+        .ok_or_else(|| crate::Error::invalid("the product of the ways overflows"))
+}
 
-    let mut cnt = 0;
-    for cand in 1..time {
-        if cand * (time - cand) > dist {
-            cnt += 1;
-        }
-    }
-    Ok(cnt)
+pub fn part2(races: &Races) -> crate::Result<u64> {
+    let (time, dist) = races.race;
+    Ok(ways(time, dist))
 }
+
+pub const SOLUTION: crate::Solution = solution!(6, "Wait For It", parse, [part1, part2]);
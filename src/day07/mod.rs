@@ -1,11 +1,6 @@
 use std::collections::HashSet;
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
-fn card_value(card: char, is_p2: bool) -> crate::Result<usize> {
+fn card_value(card: char, is_p2: bool) -> Option<usize> {
     let val = match card {
         '2' => 2,
         '3' => 3,
@@ -26,9 +21,9 @@ fn card_value(card: char, is_p2: bool) -> crate::Result<usize> {
         'Q' => 12,
         'K' => 13,
         'A' => 14,
-        _ => return Err(crate::Error::boxed(Error::InvalidInput)),
+        _ => return None,
     };
-    Ok(val)
+    Some(val)
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -61,40 +56,55 @@ impl Hand {
         }
     }
 
-    fn from_str(s: &str, is_p2: bool) -> crate::Result<Self> {
-        let mut parts = s.split_whitespace();
-        let cards = parts
-            .next()
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
-            .chars()
-            .map(|c| card_value(c, is_p2))
-            .collect::<Result<_, _>>()?;
-        let bid = parts
-            .next()
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
-            .parse()?;
+    fn from_cards(cards: &str, bid: usize, is_p2: bool) -> crate::Result<Self> {
+        let cards = cards
+            .char_indices()
+            .map(|(idx, c)| {
+                card_value(c, is_p2)
+                    .ok_or_else(|| crate::Error::parse(&cards[idx..idx + c.len_utf8()], "a card"))
+            })
+            .collect::<crate::Result<_>>()?;
         Ok(Hand::new(cards, bid))
     }
 }
 
-fn solve(input: &str, is_p2: bool) -> crate::Result<usize> {
-    let mut hands = input
-        .lines()
-        .map(|l| Hand::from_str(l, is_p2))
-        .collect::<Result<Vec<_>, _>>()?;
+/// Cards and bid of each hand. The cards are valued when solving, since
+/// the value of a joker differs between the parts.
+type Hands<'a> = Vec<(&'a str, usize)>;
+
+pub fn parse(input: &str) -> crate::Result<Hands<'_>> {
+    let mut hands = Vec::new();
+    for line in input.lines() {
+        let (cards, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| crate::Error::parse(line, "'<cards> <bid>'"))?;
+        let bid = crate::error::parse(bid.trim())?;
+        hands.push((cards, bid));
+    }
+    Ok(hands)
+}
+
+fn solve(hands: &[(&str, usize)], is_p2: bool) -> crate::Result<usize> {
+    let mut hands = hands
+        .iter()
+        .map(|(cards, bid)| Hand::from_cards(cards, *bid, is_p2))
+        .collect::<crate::Result<Vec<_>>>()?;
     hands.sort();
-    let winnings = hands
+    hands
         .iter()
         .enumerate()
-        .map(|(idx, hand)| (1 + idx) * hand.bid)
-        .sum();
-    Ok(winnings)
+        .try_fold(0usize, |sum, (idx, hand)| {
+            sum.checked_add((1 + idx).checked_mul(hand.bid)?)
+        })
+        .ok_or_else(|| crate::Error::invalid("the winnings overflow"))
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    solve(input, false)
+pub fn part1(hands: &Hands) -> crate::Result<usize> {
+    solve(hands, false)
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    solve(input, true)
+pub fn part2(hands: &Hands) -> crate::Result<usize> {
+    solve(hands, true)
 }
+
+pub const SOLUTION: crate::Solution = solution!(7, "Camel Cards", parse, [part1, part2]);
@@ -1,42 +1,56 @@
 use std::collections::HashMap;
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 type Map<'a> = HashMap<&'a str, (&'a str, &'a str)>;
 
-fn parse(input: &str) -> crate::Result<(&str, Map)> {
-    if let Some((directions, network)) = input.split_once("\n\n") {
+type Network<'a> = (&'a str, Map<'a>);
+
+pub fn parse(input: &str) -> crate::Result<Network<'_>> {
+    if let Some((directions, network)) = crate::input::split_section(input) {
+        if directions.is_empty() {
+            return Err(crate::Error::parse(directions, "L or R"));
+        }
+        if let Some((idx, c)) = directions.char_indices().find(|(_, c)| !"LR".contains(*c)) {
+            let turn = &directions[idx..idx + c.len_utf8()];
+            return Err(crate::Error::parse(turn, "L or R"));
+        }
+
         let mut map = HashMap::new();
         for line in network.lines() {
-            map.insert(&line[..3], (&line[7..10], &line[12..15]));
+            let node = line.split_once(" = (").and_then(|(node, dsts)| {
+                let dsts = dsts.strip_suffix(')')?.split_once(", ")?;
+                Some((node, dsts))
+            });
+            let Some((node, dsts)) = node else {
+                return Err(crate::Error::parse(line, "'<node> = (<left>, <right>)'"));
+            };
+            map.insert(node, dsts);
         }
         Ok((directions, map))
     } else {
-        Err(crate::Error::boxed(Error::InvalidInput))
+        Err(crate::Error::parse(input, "directions and a network"))
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let (directions, map) = parse(input)?;
+pub fn part1(network: &Network) -> crate::Result<usize> {
+    let (directions, map) = network;
+    // once every node has been left by every direction, the walk goes around
+    // in circles
+    let states = map.len() * directions.len();
     let mut pos = "AAA";
     for (idx, turn) in directions.chars().cycle().enumerate() {
         if pos == "ZZZ" {
             return Ok(idx);
         }
+        if idx > states {
+            return Err(crate::Error::invalid("the walk never reaches ZZZ"));
+        }
 
         let dst = map
             .get(pos)
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-        pos = match turn {
-            'L' => dst.0,
-            'R' => dst.1,
-            _ => return Err(crate::Error::boxed(Error::InvalidInput)),
-        }
+            .ok_or_else(|| crate::Error::parse(pos, "a node in the network"))?;
+        pos = if turn == 'L' { dst.0 } else { dst.1 };
     }
-    unreachable!()
+    unreachable!("the directions are never empty")
 }
 
 fn gcd(a: i64, b: i64) -> i64 {
@@ -47,25 +61,43 @@ fn gcd(a: i64, b: i64) -> i64 {
     }
 }
 
-fn lcm(a: i64, b: i64) -> i64 {
+fn lcm(a: i64, b: i64) -> Option<i64> {
     if b == 0 || a == 0 {
-        0
+        Some(0)
     } else {
-        a * (b / gcd(a, b))
+        a.checked_mul(b / gcd(a, b))
     }
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    let (directions, map) = parse(input)?;
+pub fn part2(network: &Network) -> crate::Result<i64> {
+    let (directions, map) = network;
     let directions: Vec<char> = directions.chars().collect();
 
     let mut loops = Vec::new();
-    for mut pos in map.keys().filter(|key| key.ends_with('A')).cloned() {
+    for start in map.keys().filter(|key| key.ends_with('A')).cloned() {
+        let mut pos = start;
         let mut seen: HashMap<(&str, usize), i64> = HashMap::new();
+        // within as many steps as there are states the walk is in a circle,
+        // which is at most as long, so any end node on it is passed twice
+        // within as many steps again
+        let states = map.len() * directions.len();
         for (step_cnt, dir_idx) in (0..directions.len()).cycle().enumerate() {
+            if step_cnt > 3 * states {
+                return Err(crate::Error::invalid(format!(
+                    "the walk from {} never ends up in a loop through a node ending in Z",
+                    start
+                )));
+            }
             let step_cnt = step_cnt as i64;
             if pos.ends_with('Z') {
+                trace!(2, "{} at step {}", pos, step_cnt);
                 if let Some(start_step_cnt) = seen.get(&(pos, dir_idx)) {
+                    trace!(
+                        1,
+                        "loop of {} steps through {}",
+                        step_cnt - start_step_cnt,
+                        pos
+                    );
                     loops.push(step_cnt - start_step_cnt);
                     break;
                 }
@@ -74,13 +106,18 @@ pub fn part2(input: &str) -> crate::Result<i64> {
 
             let dst = map
                 .get(pos)
-                .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-            pos = match directions[dir_idx] {
-                'L' => dst.0,
-                'R' => dst.1,
-                _ => return Err(crate::Error::boxed(Error::InvalidInput)),
-            }
+                .ok_or_else(|| crate::Error::parse(pos, "a node in the network"))?;
+            pos = if directions[dir_idx] == 'L' {
+                dst.0
+            } else {
+                dst.1
+            };
         }
     }
-    Ok(loops.into_iter().fold(1, lcm))
+    loops
+        .into_iter()
+        .try_fold(1, lcm)
+        .ok_or_else(|| crate::Error::invalid("the number of steps overflows"))
 }
+
+pub const SOLUTION: crate::Solution = solution!(8, "Haunted Wasteland", parse, [part1, part2]);
@@ -1,37 +1,51 @@
-fn extrapolate(nums: &[i64]) -> i64 {
+fn overflow() -> crate::Error {
+    crate::Error::invalid("the history's differences overflow")
+}
+
+fn extrapolate(nums: &[i64]) -> crate::Result<i64> {
     if nums.iter().all(|n| n == &0) {
-        return 0;
+        return Ok(0);
     }
 
-    let diffs: Vec<i64> = nums.windows(2).map(|nums| nums[1] - nums[0]).collect();
-    nums.last().unwrap() + extrapolate(&diffs)
+    let diffs = nums
+        .windows(2)
+        .map(|nums| nums[1].checked_sub(nums[0]).ok_or_else(overflow))
+        .collect::<crate::Result<Vec<i64>>>()?;
+    nums.last()
+        .unwrap()
+        .checked_add(extrapolate(&diffs)?)
+        .ok_or_else(overflow)
+}
+
+/// Adds up the extrapolated values of the histories.
+fn sum(mut histories: impl Iterator<Item = crate::Result<i64>>) -> crate::Result<i64> {
+    histories.try_fold(0i64, |sum, next| {
+        sum.checked_add(next?).ok_or_else(overflow)
+    })
 }
 
-fn parse(input: &str) -> crate::Result<Vec<Vec<i64>>> {
+pub fn parse(input: &str) -> crate::Result<Vec<Vec<i64>>> {
     let nums = input
         .lines()
         .map(|line| {
             line.split_whitespace()
-                .map(str::parse)
-                .collect::<Result<Vec<i64>, _>>()
+                .map(crate::error::parse)
+                .collect::<crate::Result<Vec<i64>>>()
         })
-        .collect::<Result<_, _>>()?;
+        .collect::<crate::Result<_>>()?;
     Ok(nums)
 }
 
-pub fn part1(input: &str) -> crate::Result<i64> {
-    Ok(parse(input)?
-        .into_iter()
-        .map(|nums| extrapolate(&nums))
-        .sum())
+pub fn part1(histories: &[Vec<i64>]) -> crate::Result<i64> {
+    sum(histories.iter().map(|nums| extrapolate(nums)))
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    Ok(parse(input)?
-        .into_iter()
-        .map(|mut nums| {
-            nums.reverse();
-            extrapolate(&nums)
-        })
-        .sum())
+pub fn part2(histories: &[Vec<i64>]) -> crate::Result<i64> {
+    sum(histories.iter().map(|nums| {
+        let mut nums = nums.clone();
+        nums.reverse();
+        extrapolate(&nums)
+    }))
 }
+
+pub const SOLUTION: crate::Solution = solution!(9, "Mirage Maintenance", parse, [part1, part2]);
@@ -3,11 +3,6 @@ use std::{
     iter::repeat,
 };
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Dir {
     N,
@@ -16,8 +11,8 @@ enum Dir {
     W,
 }
 
-#[derive(Debug)]
-struct Tile {
+#[derive(Debug, Clone)]
+pub struct Tile {
     content: char,
     is_loop: bool,
     is_outside: [bool; 4], // NESW
@@ -101,15 +96,18 @@ fn find_loop(start: (i32, i32), map: &mut Map) -> crate::Result<HashSet<(i32, i3
     let mut seen = HashSet::new();
     let mut to_visit = vec![start];
     while let Some(pos) = to_visit.pop() {
-        map.get_mut(&pos).unwrap().is_loop = true;
+        let tile = map
+            .get_mut(&pos)
+            .ok_or_else(|| crate::Error::invalid("the loop runs off the map"))?;
+        tile.is_loop = true;
         if seen.insert(pos) {
-            to_visit.extend(map.get(&pos).unwrap().connections(pos));
+            to_visit.extend(tile.connections(pos));
         }
     }
     Ok(seen)
 }
 
-fn parse(input: &str) -> crate::Result<((i32, i32), Map)> {
+pub fn parse(input: &str) -> crate::Result<((i32, i32), Map)> {
     let mut map: Map = input
         .lines()
         .enumerate()
@@ -120,7 +118,7 @@ fn parse(input: &str) -> crate::Result<((i32, i32), Map)> {
         .iter()
         .find(|(_, tile)| tile.content == 'S')
         .map(|(pos, _)| pos)
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+        .ok_or_else(|| crate::Error::invalid("no starting tile 'S'"))?;
 
     // fix starting location
     let connections = [(x, y - 1), (x + 1, y), (x, y + 1), (x - 1, y)]
@@ -136,22 +134,28 @@ fn parse(input: &str) -> crate::Result<((i32, i32), Map)> {
         [false, true, true, false] => 'F',
         [false, false, true, true] => '7',
         [true, false, false, true] => 'J',
-        _ => return Err(crate::Error::boxed(Error::InvalidInput)),
+        _ => {
+            let start = input.find('S').unwrap_or_default();
+            let start = &input[start..start + 1];
+            return Err(crate::Error::parse(
+                start,
+                "a start with two connecting pipes",
+            ));
+        }
     };
 
     Ok(((x, y), map))
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let (start, mut map) = parse(input)?;
-    Ok(find_loop(start, &mut map)?.len() / 2)
+pub fn part1((start, map): &((i32, i32), Map)) -> crate::Result<usize> {
+    let mut map = map.clone();
+    Ok(find_loop(*start, &mut map)?.len() / 2)
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let (start, mut map) = parse(input)?;
-    find_loop(start, &mut map)?;
+pub fn part2((start, map): &((i32, i32), Map)) -> crate::Result<usize> {
+    let mut map = map.clone();
+    find_loop(*start, &mut map)?;
 
-    map.get_mut(&(0, 0)).unwrap().fill_from(Dir::N);
     let mut to_visit = vec![((0, 0), Dir::N)];
     let mut seen = HashSet::new();
     while let Some((pos, dir)) = to_visit.pop() {
@@ -169,65 +173,4 @@ pub fn part2(input: &str) -> crate::Result<usize> {
         .count())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn p2ex1() {
-        let input = "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........";
-        assert_eq!(4, part2(input).unwrap());
-    }
-
-    #[test]
-    fn p2ex2() {
-        let input = "..........
-.S------7.
-.|F----7|.
-.||....||.
-.||....||.
-.|L-7F-J|.
-.|..||..|.
-.L--JL--J.
-..........";
-        assert_eq!(4, part2(input).unwrap());
-    }
-
-    #[test]
-    fn p2ex3() {
-        let input = ".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...";
-        assert_eq!(8, part2(input).unwrap());
-    }
-
-    #[test]
-    fn p2ex4() {
-        let input = "FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L";
-        assert_eq!(10, part2(input).unwrap());
-    }
-}
+pub const SOLUTION: crate::Solution = solution!(10, "Pipe Maze", parse, [part1, part2]);
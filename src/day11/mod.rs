@@ -2,38 +2,53 @@ use std::cmp::{max, min};
 use std::collections::HashSet;
 use std::iter;
 
-fn solve(input: &str, expand: usize) -> crate::Result<usize> {
-    let stars: Vec<(usize, usize)> = input
+pub fn parse(input: &str) -> crate::Result<Vec<(usize, usize)>> {
+    let stars = input
         .lines()
         .enumerate()
         .flat_map(|(y, l)| iter::repeat(y).zip(l.chars().enumerate()))
         .filter(|(_, (_, c))| c == &'#')
         .map(|(y, (x, _))| (x, y))
         .collect();
+    Ok(stars)
+}
+
+fn solve(stars: &[(usize, usize)], expand: usize) -> crate::Result<usize> {
     let rows: HashSet<usize> = stars.iter().map(|(_, y)| *y).collect();
     let cols: HashSet<usize> = stars.iter().map(|(x, _)| *x).collect();
 
-    let sum = stars
-        .iter()
-        .enumerate()
-        .flat_map(|(idx, star)| iter::repeat(star).zip(&stars[idx + 1..]))
-        .map(|((x1, y1), (x2, y2))| {
-            let mut sum: usize = (1 + min(*x1, *x2)..=max(*x1, *x2))
-                .map(|x| if cols.contains(&x) { 1 } else { expand })
-                .sum::<usize>();
-            sum += (1 + min(*y1, *y2)..=max(*y1, *y2))
-                .map(|y| if rows.contains(&y) { 1 } else { expand })
-                .sum::<usize>();
-            sum
-        })
-        .sum();
+    let overflow = || crate::Error::invalid("the distances are too large to add up");
+    let dist = |from: usize, to: usize, kept: &HashSet<usize>| {
+        (1 + min(from, to)..=max(from, to))
+            .map(|n| if kept.contains(&n) { 1 } else { expand })
+            .try_fold(0usize, usize::checked_add)
+            .ok_or_else(overflow)
+    };
+
+    let mut sum = 0usize;
+    for (idx, (x1, y1)) in stars.iter().enumerate() {
+        for (x2, y2) in &stars[idx + 1..] {
+            let d = dist(*x1, *x2, &cols)?
+                .checked_add(dist(*y1, *y2, &rows)?)
+                .ok_or_else(overflow)?;
+            sum = sum.checked_add(d).ok_or_else(overflow)?;
+        }
+    }
     Ok(sum)
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    solve(input, 2)
+pub fn part1(stars: &[(usize, usize)], params: &crate::Params) -> crate::Result<usize> {
+    solve(stars, params.get("expansion1")?)
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    solve(input, 1000000)
+pub fn part2(stars: &[(usize, usize)], params: &crate::Params) -> crate::Result<usize> {
+    solve(stars, params.get("expansion2")?)
 }
+
+pub const SOLUTION: crate::Solution = solution!(
+    11,
+    "Cosmic Expansion",
+    parse,
+    [part1(params), part2(params)],
+    params[expansion1 = 2, expansion2 = 1000000]
+);
@@ -1,89 +1,94 @@
 use std::collections::HashMap;
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
-fn parse(input: &str) -> crate::Result<Vec<(&str, Vec<usize>)>> {
+pub fn parse(input: &str) -> crate::Result<Vec<(&str, Vec<usize>)>> {
     let mut res = Vec::new();
     for line in input.lines() {
         if let Some((springs, instr)) = line.split_once(' ') {
+            if let Some((idx, c)) = springs.char_indices().find(|&(_, c)| !".#?".contains(c)) {
+                let spring = &springs[idx..idx + c.len_utf8()];
+                return Err(crate::Error::parse(spring, "'.', '#' or '?'"));
+            }
             let instr = instr
                 .split(',')
-                .map(str::parse)
-                .collect::<Result<Vec<usize>, _>>()?;
+                .map(crate::error::parse)
+                .collect::<crate::Result<Vec<usize>>>()?;
             res.push((springs, instr));
         } else {
-            return Err(crate::Error::boxed(Error::InvalidInput));
+            return Err(crate::Error::parse(line, "'<springs> <groups>'"));
         }
     }
     Ok(res)
 }
 
+/// Number of ways the unknown springs can be filled in to match `instr`, or
+/// `None` if there are too many to count.
 fn num_options<'a>(
     springs: &'a str,
     instr: &'a [usize],
     solved: &mut HashMap<(&'a str, &'a [usize]), usize>,
-) -> usize {
+) -> Option<usize> {
     if let Some(num_opts) = solved.get(&(springs, instr)) {
-        return *num_opts;
+        return Some(*num_opts);
     }
 
     if instr.is_empty() {
         if springs.chars().all(|c| c != '#') {
-            return 1;
+            return Some(1);
         } else {
-            return 0;
+            return Some(0);
         };
     }
 
     let len = instr[0];
     if springs.len() < len {
-        return 0;
+        return Some(0);
     }
 
     if springs.len() == len {
         if springs.chars().all(|c| c != '.') && instr.len() == 1 {
-            return 1;
+            return Some(1);
         } else {
-            return 0;
+            return Some(0);
         };
     }
 
-    let mut num_opts = 0;
+    let mut num_opts: usize = 0;
     if springs[..len].chars().all(|c| c != '.') && springs.chars().nth(len).unwrap() != '#' {
-        num_opts += num_options(&springs[len + 1..], &instr[1..], solved);
+        num_opts = num_opts.checked_add(num_options(&springs[len + 1..], &instr[1..], solved)?)?;
     }
     if !springs.starts_with('#') {
-        num_opts += num_options(&springs[1..], instr, solved);
+        num_opts = num_opts.checked_add(num_options(&springs[1..], instr, solved)?)?;
     }
     solved.insert((springs, instr), num_opts);
-    num_opts
+    Some(num_opts)
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let sum = parse(input)?
-        .into_iter()
-        .map(|(springs, instr)| num_options(springs, &instr, &mut HashMap::new()))
-        .sum();
-    Ok(sum)
+/// Adds up the options of each record.
+fn sum<S: AsRef<str>>(mut records: impl Iterator<Item = (S, Vec<usize>)>) -> crate::Result<usize> {
+    records
+        .try_fold(0usize, |sum, (springs, instr)| {
+            sum.checked_add(num_options(springs.as_ref(), &instr, &mut HashMap::new())?)
+        })
+        .ok_or_else(|| crate::Error::invalid("too many arrangements to count"))
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let sum = parse(input)?
-        .into_iter()
-        .map(|(row, instr)| {
-            let row = [row; 5].join("?");
-            let instr: Vec<usize> = instr
-                .iter()
-                .cloned()
-                .cycle()
-                .take(5 * instr.len())
-                .collect();
-            (row, instr)
-        })
-        .map(|(springs, instr)| num_options(&springs, &instr, &mut HashMap::new()))
-        .sum();
-    Ok(sum)
+pub fn part1(records: &[(&str, Vec<usize>)]) -> crate::Result<usize> {
+    sum(records
+        .iter()
+        .map(|(springs, instr)| (*springs, instr.clone())))
 }
+
+pub fn part2(records: &[(&str, Vec<usize>)]) -> crate::Result<usize> {
+    sum(records.iter().map(|(row, instr)| {
+        let row = [*row; 5].join("?");
+        let instr: Vec<usize> = instr
+            .iter()
+            .cloned()
+            .cycle()
+            .take(5 * instr.len())
+            .collect();
+        (row, instr)
+    }))
+}
+
+pub const SOLUTION: crate::Solution = solution!(12, "Hot Springs", parse, [part1, part2]);
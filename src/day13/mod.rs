@@ -1,12 +1,12 @@
 use std::{cmp::max, collections::HashSet, iter::repeat, str::FromStr};
 
-struct Map {
+pub struct Map {
     rocks: HashSet<(i32, i32)>,
     size: (i32, i32),
 }
 
 impl FromStr for Map {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
         let mut size = (0i32, 0i32);
@@ -47,33 +47,32 @@ impl Map {
             .count()
     }
 
-    fn score(&self, smudge_count: usize) -> i32 {
+    fn score(&self, smudge_count: usize) -> crate::Result<i32> {
         if let Some(x) = (1..self.size.0).find(|x| self.smudges_x(*x) == smudge_count) {
-            return x;
+            return Ok(x);
         }
 
         if let Some(y) = (1..self.size.1).find(|y| self.smudges_y(*y) == smudge_count) {
-            return 100 * y;
+            return Ok(100 * y);
         }
 
-        unreachable!()
+        Err(crate::Error::invalid("a pattern has no line of reflection"))
     }
 }
 
-fn parse(input: &str) -> crate::Result<Vec<Map>> {
-    let maps = input
-        .split("\n\n")
+pub fn parse(input: &str) -> crate::Result<Vec<Map>> {
+    let maps = crate::input::sections(input)
         .map(Map::from_str)
-        .collect::<Result<_, _>>()?;
+        .collect::<crate::Result<_>>()?;
     Ok(maps)
 }
 
-pub fn part1(input: &str) -> crate::Result<i32> {
-    let sum = parse(input)?.into_iter().map(|map| map.score(0)).sum();
-    Ok(sum)
+pub fn part1(maps: &[Map]) -> crate::Result<i32> {
+    maps.iter().map(|map| map.score(0)).sum()
 }
 
-pub fn part2(input: &str) -> crate::Result<i32> {
-    let sum = parse(input)?.into_iter().map(|map| map.score(1)).sum();
-    Ok(sum)
+pub fn part2(maps: &[Map]) -> crate::Result<i32> {
+    maps.iter().map(|map| map.score(1)).sum()
 }
+
+pub const SOLUTION: crate::Solution = solution!(13, "Point of Incidence", parse, [part1, part2]);
@@ -1,12 +1,12 @@
 use std::{collections::HashMap, str::FromStr};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
-struct Map {
+pub struct Map {
     map: Vec<Vec<char>>,
 }
 
 impl FromStr for Map {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
         let map = s.lines().map(|line| line.chars().collect()).collect();
@@ -62,29 +62,51 @@ impl Map {
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let mut map: Map = input.parse()?;
+pub fn parse(input: &str) -> crate::Result<Map> {
+    input.parse()
+}
+
+pub fn part1(map: &Map) -> crate::Result<usize> {
+    let mut map = map.clone();
     while map.mv((0, -1)) {}
     Ok(map.score())
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let mut map: Map = input.parse()?;
+pub fn part2(map: &Map, params: &crate::Params) -> crate::Result<usize> {
+    let mut map = map.clone();
 
-    const TGT: usize = 1000000000;
+    let target: usize = params.get("cycles")?;
     let mut cycle = 0;
     let mut seen = HashMap::new();
     seen.insert(map.clone(), cycle);
-    while cycle < TGT {
+    while cycle < target {
         map.cycle();
         cycle += 1;
+        trace!(2, "load {} after cycle {}", map.score(), cycle);
 
         if let Some(first) = seen.get(&map) {
             let len = cycle - first;
-            let skip = (TGT - cycle) / len;
+            let skip = (target - cycle) / len;
+            if skip > 0 {
+                trace!(
+                    1,
+                    "cycle {} repeats cycle {}, every {} cycles",
+                    cycle,
+                    first,
+                    len
+                );
+            }
             cycle += skip * len;
         }
         seen.insert(map.clone(), cycle);
     }
     Ok(map.score())
 }
+
+pub const SOLUTION: crate::Solution = solution!(
+    14,
+    "Parabolic Reflector Dish",
+    parse,
+    [part1, part2(params)],
+    params[cycles = 1000000000]
+);
@@ -1,42 +1,50 @@
 use std::array;
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 fn hash(s: &str) -> usize {
     s.chars()
         .fold(0, |acc, c| ((acc + c as usize) * 17).rem_euclid(256))
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    Ok(input.split(',').map(hash).sum())
+pub fn parse(input: &str) -> crate::Result<Vec<&str>> {
+    Ok(input.split(',').collect())
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
+pub fn part1(steps: &[&str]) -> crate::Result<usize> {
+    Ok(steps.iter().map(|s| hash(s)).sum())
+}
+
+pub fn part2(steps: &[&str]) -> crate::Result<usize> {
     let mut boxes: [Vec<(String, u32)>; 256] = array::from_fn(|_| Vec::new());
-    for instr in input.split(',') {
+    for instr in steps {
         if let Some((label, focal_length)) = instr.split_once('=') {
             let lens_box = &mut boxes[hash(label)];
             if let Some(lens) = lens_box.iter_mut().find(|(l, _)| l == label) {
-                lens.1 = focal_length.parse()?;
+                lens.1 = crate::error::parse(focal_length)?;
             } else {
-                lens_box.push((label.to_string(), focal_length.parse()?));
+                lens_box.push((label.to_string(), crate::error::parse(focal_length)?));
             }
         } else if let Some((label, _)) = instr.split_once('-') {
             let lens_box = &mut boxes[hash(label)];
             lens_box.retain(|(l, _)| l != label);
         } else {
-            return Err(crate::Error::boxed(Error::InvalidInput));
+            return Err(crate::Error::parse(
+                instr,
+                "'<label>=<focal length>' or '<label>-'",
+            ));
         }
     }
 
-    let mut sum = 0;
+    let overflow = || crate::Error::invalid("the focusing power overflows");
+    let mut sum: usize = 0;
     for (box_num, lens_box) in boxes.into_iter().enumerate() {
         for (slot_num, (_, focal_length)) in lens_box.into_iter().enumerate() {
-            sum += (1 + box_num) * (1 + slot_num) * focal_length as usize;
+            let power = ((1 + box_num) * (1 + slot_num))
+                .checked_mul(focal_length as usize)
+                .ok_or_else(overflow)?;
+            sum = sum.checked_add(power).ok_or_else(overflow)?;
         }
     }
     Ok(sum)
 }
+
+pub const SOLUTION: crate::Solution = solution!(15, "Lens Library", parse, [part1, part2]);
@@ -48,7 +48,9 @@ impl Beam {
     }
 }
 
-fn parse(input: &str) -> (HashMap<(i32, i32), char>, (i32, i32)) {
+type Contraption = (HashMap<(i32, i32), char>, (i32, i32));
+
+pub fn parse(input: &str) -> crate::Result<Contraption> {
     let mut sz = (0, 0);
     let mut map = HashMap::new();
     for (y, l) in input.lines().enumerate() {
@@ -58,7 +60,7 @@ fn parse(input: &str) -> (HashMap<(i32, i32), char>, (i32, i32)) {
             map.insert((x as i32, y as i32), c);
         }
     }
-    (map, sz)
+    Ok((map, sz))
 }
 
 fn shine(beam: Beam, map: &HashMap<(i32, i32), char>) -> usize {
@@ -79,21 +81,22 @@ fn shine(beam: Beam, map: &HashMap<(i32, i32), char>) -> usize {
     energized.len()
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let (map, _) = parse(input);
-    Ok(shine(Beam::new((-1, 0), Dir::East), &map))
+pub fn part1((map, _): &Contraption) -> crate::Result<usize> {
+    Ok(shine(Beam::new((-1, 0), Dir::East), map))
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let (map, sz) = parse(input);
+pub fn part2((map, sz): &Contraption) -> crate::Result<usize> {
     let mut max = 0;
     for x in 0..sz.0 {
-        max = max.max(shine(Beam::new((x, -1), Dir::South), &map));
-        max = max.max(shine(Beam::new((x, sz.1), Dir::North), &map));
+        max = max.max(shine(Beam::new((x, -1), Dir::South), map));
+        max = max.max(shine(Beam::new((x, sz.1), Dir::North), map));
     }
     for y in 0..sz.1 {
-        max = max.max(shine(Beam::new((-1, y), Dir::East), &map));
-        max = max.max(shine(Beam::new((sz.0, y), Dir::West), &map));
+        max = max.max(shine(Beam::new((-1, y), Dir::East), map));
+        max = max.max(shine(Beam::new((sz.0, y), Dir::West), map));
     }
     Ok(max)
 }
+
+pub const SOLUTION: crate::Solution =
+    solution!(16, "The Floor Will Be Lava", parse, [part1, part2]);
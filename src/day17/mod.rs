@@ -3,11 +3,6 @@ use std::{
     iter,
 };
 
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 enum Direction {
     North,
@@ -45,22 +40,22 @@ const DIRS: [Direction; 4] = [
 
 type Map = HashMap<(i32, i32), u32>;
 
-fn parse(input: &str) -> crate::Result<(Map, (i32, i32))> {
+pub fn parse(input: &str) -> crate::Result<(Map, (i32, i32))> {
     let mut sz = (0, 0);
     let map = input
         .lines()
         .enumerate()
-        .flat_map(|(y, l)| iter::repeat(y).zip(l.chars().enumerate()))
-        .map(|(y, (x, c))| {
+        .flat_map(|(y, l)| iter::repeat((y, l)).zip(l.char_indices()))
+        .map(|((y, l), (x, c))| {
             sz.0 = sz.0.max(1 + x as i32);
             sz.1 = sz.1.max(1 + y as i32);
             if let Some(n) = c.to_digit(10) {
                 Ok(((x as i32, y as i32), n))
             } else {
-                Err(crate::Error::boxed(Error::InvalidInput))
+                Err(crate::Error::parse(&l[x..x + c.len_utf8()], "a digit"))
             }
         })
-        .collect::<Result<_, _>>()?;
+        .collect::<crate::Result<_>>()?;
     Ok((map, sz))
 }
 
@@ -68,19 +63,29 @@ type State = ((i32, i32), Option<(Direction, u32)>);
 type Frontier = BTreeMap<u32, Vec<State>>;
 type Seen = HashSet<State>;
 
-fn search(map: &Map, tgt: (i32, i32), min_straight: u32, max_straight: u32) -> u32 {
+/// Finds the least heat loss on a path from the top left to `tgt`, moving
+/// between `min_straight` and `max_straight` blocks before turning.
+pub fn search(
+    map: &Map,
+    tgt: (i32, i32),
+    min_straight: u32,
+    max_straight: u32,
+) -> crate::Result<u32> {
     let mut frontier = Frontier::new();
     frontier.insert(0, vec![((0, 0), None)]);
     let mut seen = Seen::new();
     loop {
-        let (cost, mut states) = frontier.pop_first().unwrap();
+        let Some((cost, mut states)) = frontier.pop_first() else {
+            return Err(crate::Error::invalid("no path reaches the bottom right"));
+        };
         let (pos, path) = states.pop().unwrap();
         if !states.is_empty() {
             frontier.insert(cost, states);
         }
 
-        if pos == tgt {
-            return cost;
+        // the crucible can only stop after moving at least `min_straight`
+        if pos == tgt && path.is_none_or(|(_, straight)| straight >= min_straight) {
+            return Ok(cost);
         }
 
         for new_dir in DIRS {
@@ -120,12 +125,12 @@ fn search(map: &Map, tgt: (i32, i32), min_straight: u32, max_straight: u32) -> u
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<u32> {
-    let (map, sz) = parse(input)?;
-    Ok(search(&map, (sz.0 - 1, sz.1 - 1), 0, 3))
+pub fn part1((map, sz): &(Map, (i32, i32))) -> crate::Result<u32> {
+    search(map, (sz.0 - 1, sz.1 - 1), 0, 3)
 }
 
-pub fn part2(input: &str) -> crate::Result<u32> {
-    let (map, sz) = parse(input)?;
-    Ok(search(&map, (sz.0 - 1, sz.1 - 1), 4, 10))
+pub fn part2((map, sz): &(Map, (i32, i32))) -> crate::Result<u32> {
+    search(map, (sz.0 - 1, sz.1 - 1), 4, 10)
 }
+
+pub const SOLUTION: crate::Solution = solution!(17, "Clumsy Crucible", parse, [part1, part2]);
@@ -1,10 +1,5 @@
-#[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     North,
     East,
     South,
@@ -12,93 +7,110 @@ enum Direction {
 }
 
 impl Direction {
-    fn mv(&self, pos: (i64, i64), steps: i64) -> (i64, i64) {
+    /// The position `steps` away from `pos`, or `None` if it is out of range
+    fn mv(&self, pos: (i64, i64), steps: i64) -> Option<(i64, i64)> {
         match self {
-            Direction::North => (pos.0, pos.1 - steps),
-            Direction::East => (pos.0 + steps, pos.1),
-            Direction::South => (pos.0, pos.1 + steps),
-            Direction::West => (pos.0 - steps, pos.1),
+            Direction::North => Some((pos.0, pos.1.checked_sub(steps)?)),
+            Direction::East => Some((pos.0.checked_add(steps)?, pos.1)),
+            Direction::South => Some((pos.0, pos.1.checked_add(steps)?)),
+            Direction::West => Some((pos.0.checked_sub(steps)?, pos.1)),
         }
     }
 }
 
-fn parse(input: &str) -> crate::Result<Vec<(Direction, i64)>> {
+fn parse_p1(input: &str) -> crate::Result<Vec<(Direction, i64)>> {
     let mut res = Vec::new();
     for line in input.lines() {
         let mut parts = line.split_whitespace();
-        let dir = match parts
-            .next()
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
-        {
+        let (Some(dir), Some(steps)) = (parts.next(), parts.next()) else {
+            return Err(crate::Error::parse(
+                line,
+                "'<direction> <steps> (#<color>)'",
+            ));
+        };
+        let dir = match dir {
             "U" => Direction::North,
             "R" => Direction::East,
             "D" => Direction::South,
             "L" => Direction::West,
-            _ => return Err(crate::Error::boxed(Error::InvalidInput)),
+            _ => return Err(crate::Error::parse(dir, "U, R, D or L")),
         };
-        let steps = parts
-            .next()
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
-            .parse()?;
+        let steps = crate::error::parse(steps)?;
         res.push((dir, steps));
     }
     Ok(res)
 }
 
-fn shoelace(vertices: &[(i64, i64)]) -> i64 {
-    let mut area = 0;
+/// Twice the area enclosed by `vertices`, or `None` if it overflows
+fn shoelace(vertices: &[(i64, i64)]) -> Option<i64> {
+    let mut area: i64 = 0;
     let n = vertices.len() - 1;
-    for idx in 0..n {
-        area += vertices[idx].0 * vertices[idx + 1].1;
-        area -= vertices[idx + 1].0 * vertices[idx].1;
+    for idx in 0..=n {
+        let (a, b) = (vertices[idx], vertices[(idx + 1) % (n + 1)]);
+        area = area
+            .checked_add(a.0.checked_mul(b.1)?)?
+            .checked_sub(b.0.checked_mul(a.1)?)?;
     }
-    area += vertices[n].0 * vertices[0].1;
-    area -= vertices[0].0 * vertices[n].1;
-    area / 2
+    Some(area)
 }
 
-fn solve(instr: &[(Direction, i64)]) -> i64 {
-    let (mut x, mut y) = (0i64, 0i64);
-    let vertices: Vec<_> = instr
+fn solve(instr: &[(Direction, i64)]) -> crate::Result<i64> {
+    let overflow = || crate::Error::invalid("the lagoon is too large to measure");
+    let (mut pos, mut vertices) = ((0, 0), Vec::new());
+    for (dir, steps) in instr {
+        pos = dir.mv(pos, *steps).ok_or_else(overflow)?;
+        vertices.push(pos);
+    }
+    let area = shoelace(&vertices).ok_or_else(overflow)? / 2;
+    let border = instr
         .iter()
-        .map(|(dir, steps)| {
-            (x, y) = dir.mv((x, y), *steps);
-            (x, y)
-        })
-        .collect();
-    let area = shoelace(&vertices);
-    let border: i64 = instr.iter().map(|(_, steps)| steps).sum();
-    area + border / 2 + 1
-}
-
-pub fn part1(input: &str) -> crate::Result<i64> {
-    Ok(solve(&parse(input)?))
+        .try_fold(0i64, |sum, (_, steps)| sum.checked_add(*steps))
+        .ok_or_else(overflow)?;
+    area.checked_add(border / 2 + 1).ok_or_else(overflow)
 }
 
 fn parse_p2(input: &str) -> crate::Result<Vec<(Direction, i64)>> {
     let mut res = Vec::new();
     for line in input.lines() {
-        let mut parts = line.split_whitespace();
-        let hex = parts
+        let hex = line
+            .split_whitespace()
             .nth(2)
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-        let dir = match hex
-            .chars()
-            .nth(7)
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?
-        {
-            '0' => Direction::East,
-            '1' => Direction::South,
-            '2' => Direction::West,
-            '3' => Direction::North,
-            _ => return Err(crate::Error::boxed(Error::InvalidInput)),
+            .ok_or_else(|| crate::Error::parse(line, "'<direction> <steps> (#<color>)'"))?;
+        let digits = hex
+            .strip_prefix("(#")
+            .and_then(|hex| hex.strip_suffix(')'))
+            .filter(|digits| digits.len() == 6 && digits.is_ascii())
+            .ok_or_else(|| crate::Error::parse(hex, "'(#<6 hex digits>)'"))?;
+        let dir = match &digits[5..] {
+            "0" => Direction::East,
+            "1" => Direction::South,
+            "2" => Direction::West,
+            "3" => Direction::North,
+            dir => return Err(crate::Error::parse(dir, "0, 1, 2 or 3")),
         };
-        let steps = i64::from_str_radix(&hex[2..7], 16)?;
+        let steps = i64::from_str_radix(&digits[..5], 16)
+            .map_err(|_| crate::Error::parse(&digits[..5], "a hex number"))?;
         res.push((dir, steps));
     }
     Ok(res)
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    Ok(solve(&parse_p2(input)?))
+/// The dig plan, as read by part 1 and by part 2
+type Plans = (Vec<(Direction, i64)>, Vec<(Direction, i64)>);
+
+pub fn parse(input: &str) -> crate::Result<Plans> {
+    if input.is_empty() {
+        return Err(crate::Error::parse(input, "a dig plan"));
+    }
+    Ok((parse_p1(input)?, parse_p2(input)?))
+}
+
+pub fn part1((plan, _): &Plans) -> crate::Result<i64> {
+    solve(plan)
 }
+
+pub fn part2((_, plan): &Plans) -> crate::Result<i64> {
+    solve(plan)
+}
+
+pub const SOLUTION: crate::Solution = solution!(18, "Lavaduct Lagoon", parse, [part1, part2]);
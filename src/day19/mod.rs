@@ -1,16 +1,14 @@
 use std::collections::HashMap;
 
 #[derive(Debug)]
-enum Error {
-    InvalidInput,
-}
-
-#[derive(Debug)]
-struct Part(u64, u64, u64, u64);
+pub struct Part(u64, u64, u64, u64);
 
 impl Part {
-    fn sum(self) -> u64 {
-        self.0 + self.1 + self.2 + self.3
+    fn sum(&self) -> Option<u64> {
+        self.0
+            .checked_add(self.1)?
+            .checked_add(self.2)?
+            .checked_add(self.3)
     }
 }
 
@@ -23,37 +21,72 @@ struct PartRange {
 }
 
 impl PartRange {
-    fn count(&self) -> u64 {
-        (1 + self.xrange.1 - self.xrange.0)
-            * (1 + self.mrange.1 - self.mrange.0)
-            * (1 + self.arange.1 - self.arange.0)
-            * (1 + self.srange.1 - self.srange.0)
+    /// Number of parts in the range, or `None` if there are too many to count
+    fn count(&self) -> Option<u64> {
+        [self.xrange, self.mrange, self.arange, self.srange]
+            .iter()
+            .try_fold(1u64, |count, (lo, hi)| count.checked_mul(1 + hi - lo))
+    }
+}
+
+/// The category of a part's ratings that a rule looks at
+#[derive(Debug, Clone, Copy)]
+enum Category {
+    X,
+    M,
+    A,
+    S,
+}
+
+impl Category {
+    fn rating(self, part: &Part) -> u64 {
+        match self {
+            Category::X => part.0,
+            Category::M => part.1,
+            Category::A => part.2,
+            Category::S => part.3,
+        }
+    }
+
+    fn range(self, range: &mut PartRange) -> &mut (u64, u64) {
+        match self {
+            Category::X => &mut range.xrange,
+            Category::M => &mut range.mrange,
+            Category::A => &mut range.arange,
+            Category::S => &mut range.srange,
+        }
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Lt,
+    Gt,
+}
+
 #[derive(Debug)]
 enum Rule<'a> {
-    Conditional((char, char, u64, &'a str)),
+    Conditional((Category, Op, u64, &'a str)),
     Unconditional(&'a str),
 }
 
-impl Rule<'_> {
+impl<'a> Rule<'a> {
+    /// Where the rule sends the parts it matches
+    fn target(&self) -> &'a str {
+        match self {
+            Self::Conditional((_, _, _, res)) | Self::Unconditional(res) => res,
+        }
+    }
+
     fn apply(&self, part: &Part) -> Option<&str> {
         match self {
-            Self::Conditional((prop, op, val, res)) => {
-                let lhs = match prop {
-                    'x' => part.0,
-                    'm' => part.1,
-                    'a' => part.2,
-                    's' => part.3,
-                    _ => unreachable!(),
+            Self::Conditional((category, op, val, res)) => {
+                let lhs = category.rating(part);
+                let matches = match op {
+                    Op::Lt => lhs < *val,
+                    Op::Gt => lhs > *val,
                 };
-
-                if match op {
-                    '<' => lhs < *val,
-                    '>' => lhs > *val,
-                    _ => unreachable!(),
-                } {
+                if matches {
                     Some(res)
                 } else {
                     None
@@ -65,26 +98,19 @@ impl Rule<'_> {
 
     fn apply_range(&self, range: PartRange) -> (&str, Option<PartRange>, Option<PartRange>) {
         match self {
-            Self::Conditional((prop, op, val, res)) => {
-                let (mut matched, mut unmatched) = (range.clone(), range.clone());
-                let (range, matched_range, unmatched_range) = match prop {
-                    'x' => (range.xrange, &mut matched.xrange, &mut unmatched.xrange),
-                    'm' => (range.mrange, &mut matched.mrange, &mut unmatched.mrange),
-                    'a' => (range.arange, &mut matched.arange, &mut unmatched.arange),
-                    's' => (range.srange, &mut matched.srange, &mut unmatched.srange),
-                    _ => unreachable!(),
-                };
-
+            Self::Conditional((category, op, val, res)) => {
+                let (mut matched, mut unmatched) = (range.clone(), range);
+                let (lo, hi) = *category.range(&mut matched);
                 match op {
-                    '<' => {
-                        if range.0 < *val {
-                            if range.1 < *val {
+                    Op::Lt => {
+                        if lo < *val {
+                            if hi < *val {
                                 // full range matches
                                 (res, Some(matched), None)
                             } else {
                                 // part matches
-                                matched_range.1 = *val - 1;
-                                unmatched_range.0 = *val;
+                                category.range(&mut matched).1 = *val - 1;
+                                category.range(&mut unmatched).0 = *val;
                                 (res, Some(matched), Some(unmatched))
                             }
                         } else {
@@ -92,15 +118,15 @@ impl Rule<'_> {
                             (res, None, Some(unmatched))
                         }
                     }
-                    '>' => {
-                        if range.1 > *val {
-                            if range.0 > *val {
+                    Op::Gt => {
+                        if hi > *val {
+                            if lo > *val {
                                 // full range matches
                                 (res, Some(matched), None)
                             } else {
                                 // part matches
-                                matched_range.0 = *val + 1;
-                                unmatched_range.1 = *val;
+                                category.range(&mut matched).0 = *val + 1;
+                                category.range(&mut unmatched).1 = *val;
                                 (res, Some(matched), Some(unmatched))
                             }
                         } else {
@@ -108,31 +134,39 @@ impl Rule<'_> {
                             (res, None, Some(unmatched))
                         }
                     }
-                    _ => unreachable!(),
                 }
             }
-            Self::Unconditional(res) => (res, Some(range.clone()), None),
+            Self::Unconditional(res) => (res, Some(range), None),
         }
     }
 }
 
+/// Rules that are tried in order, and where parts go if none match
 #[derive(Debug)]
-struct Flow<'a>(Vec<Rule<'a>>);
+pub struct Flow<'a> {
+    rules: Vec<Rule<'a>>,
+    fallback: &'a str,
+}
+
+impl<'a> Flow<'a> {
+    /// Where the workflow may send parts
+    fn targets(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.rules.iter().map(Rule::target).chain([self.fallback])
+    }
 
-impl Flow<'_> {
     fn apply(&self, part: &Part) -> &str {
-        for rule in &self.0 {
+        for rule in &self.rules {
             if let Some(res) = rule.apply(part) {
                 return res;
             }
         }
-        unreachable!()
+        self.fallback
     }
 
     fn apply_range(&self, range: PartRange) -> Vec<(&str, PartRange)> {
         let mut matched = Vec::new();
         let mut unmatched = vec![range.clone()];
-        for rule in &self.0 {
+        for rule in &self.rules {
             let mut tmp = Vec::new();
             for range in unmatched {
                 let (res, m, u) = rule.apply_range(range);
@@ -145,56 +179,106 @@ impl Flow<'_> {
             }
             unmatched = tmp;
         }
-        assert!(unmatched.is_empty());
+        matched.extend(unmatched.into_iter().map(|range| (self.fallback, range)));
         matched
     }
 }
 
 type Flows<'a> = HashMap<&'a str, Flow<'a>>;
 
-fn parse(input: &str) -> crate::Result<(Flows, Vec<Part>)> {
-    let (flow_str, part_str) = input
-        .split_once("\n\n")
-        .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+type System<'a> = (Flows<'a>, Vec<Part>);
+
+/// Fails if some workflows send parts around in a circle, which they would
+/// never leave.
+///
+/// Removes workflows that no other workflow sends parts to, until either none
+/// are left or those that are left form a circle.
+fn check_acyclic<'a>(flows: &Flows<'a>) -> crate::Result<()> {
+    let targets = |flow: &Flow<'a>| {
+        flow.targets()
+            .filter(|id| flows.contains_key(id))
+            .collect::<Vec<_>>()
+    };
+    let mut sources: HashMap<&str, usize> = flows.keys().map(|&id| (id, 0)).collect();
+    for flow in flows.values() {
+        for id in targets(flow) {
+            *sources.get_mut(id).unwrap() += 1;
+        }
+    }
+
+    let mut unsent: Vec<&str> = sources
+        .iter()
+        .filter(|(_, &cnt)| cnt == 0)
+        .map(|(&id, _)| id)
+        .collect();
+    let mut removed = 0;
+    while let Some(id) = unsent.pop() {
+        removed += 1;
+        for id in targets(&flows[id]) {
+            let cnt = sources.get_mut(id).unwrap();
+            *cnt -= 1;
+            if *cnt == 0 {
+                unsent.push(id);
+            }
+        }
+    }
+    if removed < flows.len() {
+        return Err(crate::Error::invalid(
+            "some workflows send parts around in a circle",
+        ));
+    }
+    Ok(())
+}
+
+pub fn parse(input: &str) -> crate::Result<System<'_>> {
+    let (flow_str, part_str) = crate::input::split_section(input)
+        .ok_or_else(|| crate::Error::parse(input, "workflows and parts"))?;
 
     let mut flows = Flows::new();
     for flow in flow_str.lines() {
-        let curly = flow
-            .find('{')
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+        let (id, rule_str) = flow
+            .split_once('{')
+            .and_then(|(id, rules)| Some((id, rules.strip_suffix('}')?)))
+            .ok_or_else(|| crate::Error::parse(flow, "'<name>{<rules>}'"))?;
 
-        let id = &flow[..curly];
         let mut rules = Vec::new();
-        for rule in flow[curly + 1..flow.len() - 1].split(',') {
+        for rule in rule_str.split(',') {
             if let Some((cond, result)) = rule.split_once(':') {
-                let prop = cond
-                    .chars()
-                    .next()
-                    .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-                let op = cond
-                    .chars()
-                    .nth(1)
-                    .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-                if op != '<' && op != '>' {
-                    return Err(crate::Error::boxed(Error::InvalidInput));
-                }
-                let val: u64 = cond[2..].parse()?;
-                rules.push(Rule::Conditional((prop, op, val, result)));
+                let mut chars = cond.chars();
+                let category = match chars.next() {
+                    Some('x') => Category::X,
+                    Some('m') => Category::M,
+                    Some('a') => Category::A,
+                    Some('s') => Category::S,
+                    _ => return Err(crate::Error::parse(cond, "'<category><op><value>'")),
+                };
+                let op = match chars.next() {
+                    Some('<') => Op::Lt,
+                    Some('>') => Op::Gt,
+                    _ => return Err(crate::Error::parse(cond, "'<category><op><value>'")),
+                };
+                let val: u64 = crate::error::parse(chars.as_str())?;
+                rules.push(Rule::Conditional((category, op, val, result)));
             } else {
                 rules.push(Rule::Unconditional(rule));
             }
         }
-        flows.insert(id, Flow(rules));
+        let Some(Rule::Unconditional(fallback)) = rules.pop() else {
+            return Err(crate::Error::parse(rule_str, "rules ending in a fallback"));
+        };
+        flows.insert(id, Flow { rules, fallback });
     }
 
+    check_acyclic(&flows)?;
+
     let mut parts = Vec::new();
     for part in part_str.lines() {
         let values = part
             .split(',')
-            .map(|s| s.trim_matches(|c: char| !c.is_ascii_digit()).parse::<u64>())
-            .collect::<Result<Vec<u64>, _>>()?;
+            .map(|s| crate::error::parse(s.trim_matches(|c: char| !c.is_ascii_digit())))
+            .collect::<crate::Result<Vec<u64>>>()?;
         if values.len() != 4 {
-            return Err(crate::Error::boxed(Error::InvalidInput));
+            return Err(crate::Error::parse(part, "four ratings"));
         }
         parts.push(Part(values[0], values[1], values[2], values[3]));
     }
@@ -202,44 +286,57 @@ fn parse(input: &str) -> crate::Result<(Flows, Vec<Part>)> {
     Ok((flows, parts))
 }
 
-pub fn part1(input: &str) -> crate::Result<u64> {
-    let (flows, parts) = parse(input)?;
+pub fn part1((flows, parts): &System) -> crate::Result<u64> {
     let mut sum = 0;
     for part in parts {
         let mut id = "in";
         while id != "A" && id != "R" {
             let flow = flows
                 .get(id)
-                .ok_or(crate::Error::boxed(Error::InvalidInput))?;
-            id = flow.apply(&part);
+                .ok_or_else(|| crate::Error::parse(id, "a known workflow"))?;
+            id = flow.apply(part);
         }
 
         if id == "A" {
-            sum += part.sum();
+            sum = part
+                .sum()
+                .and_then(|ratings| ratings.checked_add(sum))
+                .ok_or_else(|| crate::Error::invalid("the sum of the ratings overflows"))?;
         }
     }
     Ok(sum)
 }
 
-pub fn part2(input: &str) -> crate::Result<u64> {
-    let (flows, _) = parse(input)?;
+pub fn part2((flows, _): &System, params: &crate::Params) -> crate::Result<u64> {
+    let (min, max) = (params.get("min")?, params.get("max")?);
+    if min > max {
+        return Err(crate::Error::invalid("the ratings range is empty"));
+    }
     let mut sum = 0;
     let mut ranges = vec![(
         "in",
         PartRange {
-            xrange: (1, 4000),
-            mrange: (1, 4000),
-            arange: (1, 4000),
-            srange: (1, 4000),
+            xrange: (min, max),
+            mrange: (min, max),
+            arange: (min, max),
+            srange: (min, max),
         },
     )];
     while let Some((id, range)) = ranges.pop() {
         let flow = flows
             .get(id)
-            .ok_or(crate::Error::boxed(Error::InvalidInput))?;
+            .ok_or_else(|| crate::Error::parse(id, "a known workflow"))?;
         for (id, range) in flow.apply_range(range) {
             match id {
-                "A" => sum += range.count(),
+                "A" => {
+                    trace!(2, "accepted {:?}", range);
+                    sum = range
+                        .count()
+                        .and_then(|count| count.checked_add(sum))
+                        .ok_or_else(|| {
+                            crate::Error::invalid("too many combinations of ratings to count")
+                        })?;
+                }
                 "R" => (),
                 id => ranges.push((id, range)),
             }
@@ -247,3 +344,11 @@ pub fn part2(input: &str) -> crate::Result<u64> {
     }
     Ok(sum)
 }
+
+pub const SOLUTION: crate::Solution = solution!(
+    19,
+    "Aplenty",
+    parse,
+    [part1, part2(params)],
+    params[min = 1, max = 4000]
+);
@@ -35,7 +35,7 @@ enum Kind<'a> {
 }
 
 #[derive(Debug, Clone)]
-struct Module<'a> {
+pub struct Module<'a> {
     kind: Kind<'a>,
     outputs: Vec<&'a str>,
 }
@@ -73,29 +73,33 @@ impl<'a> Module<'a> {
     }
 }
 
-fn parse(input: &str) -> HashMap<&str, Module> {
+type Modules<'a> = HashMap<&'a str, Module<'a>>;
+
+pub fn parse(input: &str) -> crate::Result<Modules<'_>> {
     let mut modules = HashMap::new();
     let mut connectivity = HashMap::new();
     for line in input.lines() {
-        let (id, outputs) = line.split_once(" -> ").unwrap();
+        let (id, outputs) = line
+            .split_once(" -> ")
+            .ok_or_else(|| crate::Error::parse(line, "'<module> -> <outputs>'"))?;
         let outputs: Vec<&str> = outputs.split(", ").collect();
-        match id.chars().next().unwrap() {
-            'b' => {
+        match id.chars().next() {
+            Some('b') => {
                 connectivity.insert(id, outputs.clone());
                 modules.insert(id, Module::new(Kind::Broadcast, outputs));
             }
-            '%' => {
+            Some('%') => {
                 connectivity.insert(&id[1..], outputs.clone());
                 modules.insert(&id[1..], Module::new(Kind::FlipFlop(State::Low), outputs));
             }
-            '&' => {
+            Some('&') => {
                 connectivity.insert(&id[1..], outputs.clone());
                 modules.insert(
                     &id[1..],
                     Module::new(Kind::Conjunction(HashMap::new()), outputs),
                 );
             }
-            _ => unreachable!(),
+            _ => return Err(crate::Error::parse(id, "a broadcaster, '%' or '&' module")),
         }
     }
 
@@ -107,34 +111,53 @@ fn parse(input: &str) -> HashMap<&str, Module> {
         }
     }
 
-    modules
+    Ok(modules)
+}
+
+/// Most pulses a button press may cause before the modules are taken to pass
+/// pulses around forever
+const MAX_PULSES: usize = 1 << 20;
+
+/// Presses the button and returns the pulses that were sent, in order,
+/// starting with the button's.
+fn press<'a>(modules: &mut Modules<'a>) -> crate::Result<Vec<Pulse<'a>>> {
+    let mut sent = Vec::new();
+    let mut pulse_queue = VecDeque::new();
+    pulse_queue.push_back(Pulse::new("button", State::Low));
+
+    while let Some(pulse) = pulse_queue.pop_front() {
+        if sent.len() == MAX_PULSES {
+            return Err(crate::Error::invalid(
+                "the modules pass pulses around forever",
+            ));
+        }
+        let outputs = if let Some(module) = modules.get(pulse.src) {
+            module.outputs.clone()
+        } else {
+            vec!["broadcaster"]
+        };
+
+        for target in outputs {
+            if let Some(module) = modules.get_mut(target) {
+                if let Some(state) = module.pulse(pulse.src, pulse.state) {
+                    pulse_queue.push_back(Pulse::new(target, state));
+                }
+            }
+        }
+        sent.push(pulse);
+    }
+    Ok(sent)
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let mut modules = parse(input);
+pub fn part1(modules: &Modules) -> crate::Result<usize> {
+    let mut modules = modules.clone();
     let (mut low, mut high) = (0, 0);
     for _ in 0..1000 {
-        let mut pulse_queue = VecDeque::new();
-        pulse_queue.push_back(Pulse::new("button", State::Low));
-
-        while let Some(pulse) = pulse_queue.pop_front() {
-            let outputs = if let Some(module) = modules.get(pulse.src) {
-                module.outputs.clone()
-            } else {
-                vec!["broadcaster"]
-            };
-
+        for pulse in press(&mut modules)? {
+            let outputs = modules.get(pulse.src).map_or(1, |m| m.outputs.len());
             match pulse.state {
-                State::Low => low += outputs.len(),
-                State::High => high += outputs.len(),
-            }
-
-            for target in outputs {
-                if let Some(module) = modules.get_mut(target) {
-                    if let Some(state) = module.pulse(pulse.src, pulse.state) {
-                        pulse_queue.push_back(Pulse::new(target, state));
-                    }
-                }
+                State::Low => low += outputs,
+                State::High => high += outputs,
             }
         }
     }
@@ -149,55 +172,60 @@ fn gcd(a: usize, b: usize) -> usize {
     }
 }
 
-fn lcm(a: usize, b: usize) -> usize {
+fn lcm(a: usize, b: usize) -> Option<usize> {
     if b == 0 || a == 0 {
-        0
+        Some(0)
     } else {
-        a * (b / gcd(a, b))
+        a.checked_mul(b / gcd(a, b))
     }
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let modules = parse(input);
+/// Most presses to wait for an input of the last conjunction to send a high
+/// pulse. Those of the puzzle count presses with 12 bits.
+const MAX_PRESSES: usize = 1 << 16;
 
+pub fn part2(modules: &Modules) -> crate::Result<usize> {
     let last_conj = &modules
         .values()
         .find(|m| m.outputs.contains(&"rx"))
-        .unwrap();
-    let conj_inputs: Vec<&str> = if let Kind::Conjunction(mem) = &last_conj.kind {
-        mem.keys().cloned().collect()
-    } else {
-        unreachable!()
+        .ok_or_else(|| crate::Error::invalid("no module sends pulses to rx"))?;
+    let Kind::Conjunction(mem) = &last_conj.kind else {
+        return Err(crate::Error::invalid("rx is not fed by a conjunction"));
     };
+    let conj_inputs: Vec<&str> = mem.keys().cloned().collect();
 
     let mut press_cnts = Vec::new();
-    'outer: for input in conj_inputs {
+    for input in conj_inputs {
         let mut modules = modules.clone();
-        for press_cnt in 1.. {
-            let mut pulse_queue = VecDeque::new();
-            pulse_queue.push_back(Pulse::new("button", State::Low));
-
-            while let Some(pulse) = pulse_queue.pop_front() {
-                if pulse.src == input && matches!(pulse.state, State::High) {
-                    press_cnts.push(press_cnt);
-                    continue 'outer;
-                }
-
-                let outputs = if let Some(module) = modules.get(pulse.src) {
-                    module.outputs.clone()
-                } else {
-                    vec!["broadcaster"]
-                };
-
-                for target in outputs {
-                    if let Some(module) = modules.get_mut(target) {
-                        if let Some(state) = module.pulse(pulse.src, pulse.state) {
-                            pulse_queue.push_back(Pulse::new(target, state));
-                        }
-                    }
-                }
+        let mut press_cnt = 0;
+        loop {
+            if press_cnt == MAX_PRESSES {
+                return Err(crate::Error::invalid(format!(
+                    "{} sends no high pulse in {} presses",
+                    input, MAX_PRESSES
+                )));
+            }
+            press_cnt += 1;
+            let sent = press(&mut modules)?;
+            if sent
+                .iter()
+                .any(|pulse| pulse.src == input && matches!(pulse.state, State::High))
+            {
+                break;
             }
         }
+        trace!(
+            1,
+            "{} sends a high pulse after {} presses",
+            input,
+            press_cnt
+        );
+        press_cnts.push(press_cnt);
     }
-    Ok(press_cnts.into_iter().fold(1, lcm))
+    press_cnts
+        .into_iter()
+        .try_fold(1, lcm)
+        .ok_or_else(|| crate::Error::invalid("the number of presses overflows"))
 }
+
+pub const SOLUTION: crate::Solution = solution!(20, "Pulse Propagation", parse, [part1, part2]);
@@ -30,7 +30,9 @@ fn plots(map: &HashMap<(i64, i64), char>, start: (i64, i64), steps: i64) -> i64
     frontier.len() as i64
 }
 
-fn parse(input: &str) -> (HashMap<(i64, i64), char>, i64) {
+type Garden = (HashMap<(i64, i64), char>, i64);
+
+pub fn parse(input: &str) -> crate::Result<Garden> {
     let mut sz = 0;
     let map: HashMap<(i64, i64), char> = input
         .lines()
@@ -41,41 +43,76 @@ fn parse(input: &str) -> (HashMap<(i64, i64), char>, i64) {
             ((x as i64, y as i64), c)
         })
         .collect();
-    (map, sz)
+    if map.is_empty() {
+        return Err(crate::Error::parse(input, "a map of the garden"));
+    }
+    Ok((map, sz))
 }
 
-pub fn part1(input: &str) -> crate::Result<i64> {
-    let (map, sz) = parse(input);
-    Ok(plots(&map, (sz / 2, sz / 2), 64))
+pub fn part1((map, sz): &Garden, params: &crate::Params) -> crate::Result<i64> {
+    let steps = params.get::<usize>("steps1")? as i64;
+    Ok(plots(map, (sz / 2, sz / 2), steps))
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    let (map, sz) = parse(input);
-
-    const STEPS: i64 = 26501365;
-    let skip = STEPS / sz;
-    let steps_remaining = STEPS % sz;
-
-    let odd = 1 + (0..skip).map(|x| x * 4).step_by(2).sum::<i64>();
-    let even = (0..skip).map(|x| x * 4).skip(1).step_by(2).sum::<i64>();
+pub fn part2((map, sz): &Garden, params: &crate::Params) -> crate::Result<i64> {
+    let (map, sz) = (map, *sz);
 
-    let mut num_plots = 0;
-    num_plots += plots(&map, (sz / 2, sz / 2), sz) * odd;
-    num_plots += plots(&map, (sz / 2, sz / 2), sz - 1) * even;
-
-    num_plots += plots(&map, (0, sz / 2), sz - 1);
-    num_plots += plots(&map, (sz - 1, sz / 2), sz - 1);
-    num_plots += plots(&map, (sz / 2, 0), sz - 1);
-    num_plots += plots(&map, (sz / 2, sz - 1), sz - 1);
+    // the walk reaches the middle of the edge of a copy of the garden, which
+    // the formula below relies on
+    let steps: i64 = params.get("steps2")?;
+    if steps < sz || steps % sz != sz / 2 {
+        return Err(crate::Error::invalid(format!(
+            "steps2 must be {} more than a multiple of {}",
+            sz / 2,
+            sz
+        )));
+    }
+    let skip = steps / sz;
+    let steps_remaining = steps % sz;
 
-    num_plots += plots(&map, (0, 0), steps_remaining - 1) * skip;
-    num_plots += plots(&map, (0, sz - 1), steps_remaining - 1) * skip;
-    num_plots += plots(&map, (sz - 1, 0), steps_remaining - 1) * skip;
-    num_plots += plots(&map, (sz - 1, sz - 1), steps_remaining - 1) * skip;
+    // copies of the garden reached in an odd and an even number of steps,
+    // which come in rings 4 * x copies around, out to `skip`
+    let overflow = || crate::Error::invalid("too many plots to count");
+    let (rings_odd, rings_even) = ((skip + 1) / 2, skip / 2);
+    let odd = rings_odd
+        .checked_mul(rings_odd - 1)
+        .and_then(|n| n.checked_mul(4)?.checked_add(1))
+        .ok_or_else(overflow)?;
+    let even = rings_even
+        .checked_mul(rings_even)
+        .and_then(|n| n.checked_mul(4))
+        .ok_or_else(overflow)?;
 
-    num_plots += plots(&map, (0, 0), 3 * (sz / 2)) * (skip - 1);
-    num_plots += plots(&map, (0, sz - 1), 3 * (sz / 2)) * (skip - 1);
-    num_plots += plots(&map, (sz - 1, 0), 3 * (sz / 2)) * (skip - 1);
-    num_plots += plots(&map, (sz - 1, sz - 1), 3 * (sz / 2)) * (skip - 1);
-    Ok(num_plots)
+    // plots reached from a starting point, and how many copies of the garden
+    // are entered there
+    let counts = [
+        (plots(map, (sz / 2, sz / 2), sz), odd),
+        (plots(map, (sz / 2, sz / 2), sz - 1), even),
+        (plots(map, (0, sz / 2), sz - 1), 1),
+        (plots(map, (sz - 1, sz / 2), sz - 1), 1),
+        (plots(map, (sz / 2, 0), sz - 1), 1),
+        (plots(map, (sz / 2, sz - 1), sz - 1), 1),
+        (plots(map, (0, 0), steps_remaining - 1), skip),
+        (plots(map, (0, sz - 1), steps_remaining - 1), skip),
+        (plots(map, (sz - 1, 0), steps_remaining - 1), skip),
+        (plots(map, (sz - 1, sz - 1), steps_remaining - 1), skip),
+        (plots(map, (0, 0), 3 * (sz / 2)), skip - 1),
+        (plots(map, (0, sz - 1), 3 * (sz / 2)), skip - 1),
+        (plots(map, (sz - 1, 0), 3 * (sz / 2)), skip - 1),
+        (plots(map, (sz - 1, sz - 1), 3 * (sz / 2)), skip - 1),
+    ];
+    counts
+        .iter()
+        .try_fold(0i64, |sum, &(plots, copies)| {
+            sum.checked_add(plots.checked_mul(copies)?)
+        })
+        .ok_or_else(overflow)
 }
+
+pub const SOLUTION: crate::Solution = solution!(
+    21,
+    "Step Counter",
+    parse,
+    [part1(params), part2(params)],
+    params[steps1 = 64, steps2 = 26501365]
+);
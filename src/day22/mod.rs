@@ -12,8 +12,12 @@ struct Line {
 }
 
 impl Line {
-    fn new(from: Vec3, to: Vec3) -> Self {
-        Line { from, to }
+    /// The line between two ends, given in either order.
+    fn new(a: Vec3, b: Vec3) -> Self {
+        Line {
+            from: (a.0.min(b.0), a.1.min(b.1), a.2.min(b.2)),
+            to: (a.0.max(b.0), a.1.max(b.1), a.2.max(b.2)),
+        }
     }
 
     fn points(&self) -> impl Iterator<Item = Vec3> + '_ {
@@ -48,7 +52,7 @@ impl Line {
 }
 
 #[derive(Debug, Clone)]
-struct Bricks {
+pub struct Bricks {
     bricks: Vec<Line>,
     lookup: HashMap<Vec3, usize>,
 }
@@ -132,37 +136,50 @@ impl Bricks {
     }
 }
 
-fn parse(input: &str) -> Bricks {
+/// Parses the bricks and lets them settle.
+pub fn parse(input: &str) -> crate::Result<Bricks> {
     let mut bricks = Vec::new();
+    // coordinates fit in an i16, so that bricks stay short enough to walk
+    // and moving them by one can't overflow
+    let point = |s: &str| {
+        let coords = s
+            .split(',')
+            .map(crate::error::parse)
+            .collect::<crate::Result<Vec<i16>>>()?;
+        match coords[..] {
+            [x, y, z] => Ok((x.into(), y.into(), z.into())),
+            _ => Err(crate::Error::parse(s, "'<x>,<y>,<z>'")),
+        }
+    };
     for line in input.lines() {
-        let (from, to) = line.split_once('~').unwrap();
-        let mut from = from.split(',').map(|n| n.parse::<i32>().unwrap());
-        let from = (
-            from.next().unwrap(),
-            from.next().unwrap(),
-            from.next().unwrap(),
-        );
-        let mut to = to.split(',').map(|n| n.parse::<i32>().unwrap());
-        let to = (to.next().unwrap(), to.next().unwrap(), to.next().unwrap());
-        bricks.push(Line::new(from, to));
+        let (from, to) = line
+            .split_once('~')
+            .ok_or_else(|| crate::Error::parse(line, "'<from>~<to>'"))?;
+        let brick = Line::new(point(from)?, point(to)?);
+        let (from, to) = (brick.from, brick.to);
+        let extents = [from.0 != to.0, from.1 != to.1, from.2 != to.2];
+        if extents.into_iter().filter(|&extends| extends).count() > 1 {
+            return Err(crate::Error::parse(line, "a straight brick"));
+        }
+        bricks.push(brick);
     }
-    Bricks::new(bricks)
+    let mut bricks = Bricks::new(bricks);
+    bricks.fall();
+    Ok(bricks)
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let mut bricks = parse(input);
-    bricks.fall();
+pub fn part1(bricks: &Bricks) -> crate::Result<usize> {
     let disintegratable = (0..bricks.bricks.len())
         .filter(|idx| bricks.count_supported(*idx) == 0)
         .count();
     Ok(disintegratable)
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let mut bricks = parse(input);
-    bricks.fall();
+pub fn part2(bricks: &Bricks) -> crate::Result<usize> {
     let sum = (0..bricks.bricks.len())
         .map(|idx| bricks.count_supported(idx))
         .sum();
     Ok(sum)
 }
+
+pub const SOLUTION: crate::Solution = solution!(22, "Sand Slabs", parse, [part1, part2]);
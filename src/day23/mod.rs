@@ -7,7 +7,7 @@ use std::{
 type Point = (i32, i32);
 type Edges = HashMap<Point, HashSet<(Point, usize)>>;
 
-struct Map {
+pub struct Map {
     start: Point,
     end: Point,
     tiles: HashMap<Point, char>,
@@ -15,16 +15,18 @@ struct Map {
 
 impl Map {
     fn neighbors(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
-        match self.tiles.get(&pos).unwrap() {
-            '.' => vec![
+        match self.tiles.get(&pos) {
+            Some('.') => vec![
                 (pos.0 + 1, pos.1),
                 (pos.0 - 1, pos.1),
                 (pos.0, pos.1 + 1),
                 (pos.0, pos.1 - 1),
             ],
-            '>' => vec![(pos.0 + 1, pos.1)],
-            'v' => vec![(pos.0, pos.1 + 1)],
-            _ => unreachable!(),
+            Some('>') => vec![(pos.0 + 1, pos.1)],
+            Some('<') => vec![(pos.0 - 1, pos.1)],
+            Some('v') => vec![(pos.0, pos.1 + 1)],
+            Some('^') => vec![(pos.0, pos.1 - 1)],
+            _ => vec![],
         }
         .into_iter()
         .filter(|p| !matches!(self.tiles.get(p), None | Some('#')))
@@ -108,7 +110,7 @@ impl Map {
             return;
         }
 
-        for (neighbor, dist) in &edges[&pos] {
+        for (neighbor, dist) in edges.get(&pos).into_iter().flatten() {
             if path.contains(neighbor) {
                 continue;
             }
@@ -121,42 +123,59 @@ impl Map {
 }
 
 impl FromStr for Map {
-    type Err = Box<dyn std::error::Error>;
+    type Err = crate::Error;
 
     fn from_str(s: &str) -> crate::Result<Self> {
-        let mut start = (0, 0);
+        let mut start = None;
         let mut end = (0, 0);
         let tiles = s
             .lines()
             .enumerate()
-            .flat_map(|(y, l)| iter::repeat(y).zip(l.chars().enumerate()))
-            .map(|(y, (x, c))| {
+            .flat_map(|(y, l)| iter::repeat((y, l)).zip(l.char_indices()))
+            .map(|((y, l), (x, c))| {
                 let (x, y) = (x as i32, y as i32);
+                if !"#.<>^v".contains(c) {
+                    let tile = &l[x as usize..x as usize + c.len_utf8()];
+                    return Err(crate::Error::parse(tile, "a path, forest or slope"));
+                }
                 if c == '.' {
                     if y == 0 {
-                        start = (x, y);
+                        start = Some((x, y));
                     }
                     end = (x, y);
                 }
-                ((x, y), c)
+                Ok(((x, y), c))
             })
-            .collect();
+            .collect::<crate::Result<_>>()?;
+        let start = start.ok_or_else(|| crate::Error::invalid("no path in the top row"))?;
         Ok(Map { start, end, tiles })
     }
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let map: Map = input.parse()?;
+pub fn parse(input: &str) -> crate::Result<Map> {
+    input.parse()
+}
+
+fn longest(solved: &[usize]) -> crate::Result<usize> {
+    solved
+        .iter()
+        .max()
+        .copied()
+        .ok_or_else(|| crate::Error::invalid("no path reaches the end"))
+}
+
+pub fn part1(map: &Map) -> crate::Result<usize> {
     let mut solved = Vec::new();
     map.find_paths(&mut vec![map.start], &mut HashSet::new(), &mut solved);
-    Ok(*solved.iter().max().unwrap())
+    longest(&solved)
 }
 
-pub fn part2(input: &str) -> crate::Result<usize> {
-    let map: Map = input.parse()?;
+pub fn part2(map: &Map) -> crate::Result<usize> {
     let mut edges = HashMap::new();
     map.find_edges(vec![map.start], &mut edges);
     let mut solved = Vec::new();
     map.find_paths_p2(&mut vec![map.start], 0, &mut solved, &edges);
-    Ok(*solved.iter().max().unwrap())
+    longest(&solved)
 }
+
+pub const SOLUTION: crate::Solution = solution!(23, "A Long Walk", parse, [part1, part2]);
@@ -2,32 +2,48 @@ use std::collections::HashSet;
 
 type Vec3<T> = (T, T, T);
 
-fn parse<T>(input: &str) -> Vec<(Vec3<T>, Vec3<T>)>
-where
-    T: std::str::FromStr + Copy,
-    <T as std::str::FromStr>::Err: std::fmt::Debug,
-{
+type Hail = (Vec3<i64>, Vec3<i64>);
+
+pub fn parse(input: &str) -> crate::Result<Vec<Hail>> {
+    let vec3 = |s: &str| {
+        let coords = s
+            .split(',')
+            .map(|n| crate::error::parse(n.trim()))
+            .collect::<crate::Result<Vec<i64>>>()?;
+        match coords[..] {
+            [x, y, z] => Ok((x, y, z)),
+            _ => Err(crate::Error::parse(s, "'<x>, <y>, <z>'")),
+        }
+    };
     input
         .lines()
         .map(|line| {
-            let (p, v) = line.split_once(" @ ").unwrap();
-            let p: Vec<T> = p.split(", ").map(|n| n.parse().unwrap()).collect();
-            let v: Vec<T> = v.split(", ").map(|n| n.parse().unwrap()).collect();
-            ((p[0], p[1], p[2]), (v[0], v[1], v[2]))
+            let (p, v) = line
+                .split_once(" @ ")
+                .ok_or_else(|| crate::Error::parse(line, "'<position> @ <velocity>'"))?;
+            Ok((vec3(p)?, vec3(v)?))
         })
         .collect()
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    const MIN: f64 = 200000000000000.0;
-    const MAX: f64 = 400000000000000.0;
+pub fn part1(hails: &[Hail], params: &crate::Params) -> crate::Result<usize> {
+    let min = params.get::<i64>("min")? as f64;
+    let max = params.get::<i64>("max")? as f64;
     let mut cnt = 0;
-    let hails = parse::<f64>(input);
+    let hails: Vec<(Vec3<f64>, Vec3<f64>)> = hails
+        .iter()
+        .map(|&((x, y, z), (vx, vy, vz))| {
+            (
+                (x as f64, y as f64, z as f64),
+                (vx as f64, vy as f64, vz as f64),
+            )
+        })
+        .collect();
     for (idx, ((x1, y1, _), (vx1, vy1, _))) in hails.iter().enumerate() {
         for ((x2, y2, _), (vx2, vy2, _)) in &hails[1 + idx..] {
             let y = (vx1 / vy1 * y1 - vx2 / vy2 * y2 + x2 - x1) / (vx1 / vy1 - vx2 / vy2);
             let x = vx1 / vy1 * (y - y1) + x1;
-            if !(MIN..=MAX).contains(&x) || !(MIN..=MAX).contains(&y) {
+            if !(min..=max).contains(&x) || !(min..=max).contains(&y) {
                 continue;
             }
 
@@ -44,27 +60,58 @@ pub fn part1(input: &str) -> crate::Result<usize> {
     Ok(cnt)
 }
 
-pub fn part2(input: &str) -> crate::Result<i64> {
-    let hails = parse::<i64>(input);
+pub fn part2(hails: &[Hail]) -> crate::Result<i64> {
+    let [((x1, y1, z1), (vx1, vy1, vz1)), ((x2, y2, z2), (vx2, vy2, vz2)), ..] = hails[..] else {
+        return Err(crate::Error::invalid("there are fewer than two hailstones"));
+    };
 
+    // the rock shares its x position and velocity with any two hailstones
+    // that share theirs
     let mut xs = HashSet::new();
-    let (mut stone_x, mut stone_vx) = (0, 0);
-    for ((x, _, _), (vx, _, _)) in &hails {
-        if !xs.insert((x, vx)) {
-            stone_x = *x;
-            stone_vx = *vx;
-            break;
-        }
-    }
+    let (stone_x, stone_vx) = hails
+        .iter()
+        .map(|&((x, _, _), (vx, _, _))| (x, vx))
+        .find(|&xv| !xs.insert(xv))
+        .ok_or_else(|| {
+            crate::Error::invalid("no two hailstones share an x position and velocity")
+        })?;
 
-    let ((x1, y1, z1), (vx1, vy1, vz1)) = hails[0];
-    let t1 = (x1 - stone_x) / (stone_vx - vx1);
-    let ((x2, y2, z2), (vx2, vy2, vz2)) = hails[1];
-    let t2 = (x2 - stone_x) / (stone_vx - vx2);
-    let stone_vy = (y2 - y1 + t2 * vy2 - t1 * vy1) / (t2 - t1);
-    let stone_y = y1 + t1 * (vy1 - stone_vy);
-    let stone_vz = (z2 - z1 + t2 * vz2 - t1 * vz1) / (t2 - t1);
-    let stone_z = z1 + t1 * (vz1 - stone_vz);
+    let unsolvable = || crate::Error::invalid("the rock's path can't be worked out");
+    let t1 = time((x1, vx1), (stone_x, stone_vx)).ok_or_else(unsolvable)?;
+    let t2 = time((x2, vx2), (stone_x, stone_vx)).ok_or_else(unsolvable)?;
+    let stone_y = start(t1, t2, (y1, vy1), (y2, vy2)).ok_or_else(unsolvable)?;
+    let stone_z = start(t1, t2, (z1, vz1), (z2, vz2)).ok_or_else(unsolvable)?;
 
-    Ok(stone_x + stone_y + stone_z)
+    stone_x
+        .checked_add(stone_y)
+        .and_then(|sum| sum.checked_add(stone_z))
+        .ok_or_else(unsolvable)
 }
+
+/// The time at which the rock hits a hailstone, given the position and
+/// velocity of each along one axis. `None` if they never meet or the numbers
+/// overflow.
+fn time((p, v): (i64, i64), (stone_p, stone_v): (i64, i64)) -> Option<i64> {
+    p.checked_sub(stone_p)?.checked_div(stone_v.checked_sub(v)?)
+}
+
+/// The rock's starting position along one axis, given that it hits two
+/// hailstones, with the positions and velocities `(p1, v1)` and `(p2, v2)`,
+/// at times `t1` and `t2`. `None` if the times are the same or the numbers
+/// overflow.
+fn start(t1: i64, t2: i64, (p1, v1): (i64, i64), (p2, v2): (i64, i64)) -> Option<i64> {
+    let dist = p2
+        .checked_sub(p1)?
+        .checked_add(t2.checked_mul(v2)?)?
+        .checked_sub(t1.checked_mul(v1)?)?;
+    let v = dist.checked_div(t2.checked_sub(t1)?)?;
+    p1.checked_add(t1.checked_mul(v1.checked_sub(v)?)?)
+}
+
+pub const SOLUTION: crate::Solution = solution!(
+    24,
+    "Never Tell Me The Odds",
+    parse,
+    [part1(params), part2],
+    params[min = 200000000000000, max = 400000000000000]
+);
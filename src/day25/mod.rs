@@ -1,25 +1,30 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fs,
-    io::Read,
-};
+use crate::rng::Rng;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Edge<'a> {
+pub struct Edge<'a> {
     id: (&'a str, &'a str),
     ns: (&'a str, &'a str),
 }
 
-fn parse(input: &str) -> (HashMap<&str, HashSet<usize>>, HashMap<usize, Edge>) {
-    let mut edges = HashSet::new();
+// edges are numbered and kept in order, so that the contractions only depend
+// on the seed
+type Graph<'a> = (HashMap<&'a str, HashSet<usize>>, BTreeMap<usize, Edge<'a>>);
+
+pub fn parse(input: &str) -> crate::Result<Graph<'_>> {
+    let mut edges = Vec::new();
     for line in input.lines() {
-        let (n1, neighbors) = line.split_once(": ").unwrap();
+        let (n1, neighbors) = line
+            .split_once(": ")
+            .ok_or_else(|| crate::Error::parse(line, "'<component>: <components>'"))?;
         for n2 in neighbors.split_whitespace() {
             let edge = if n1 < n2 { (n1, n2) } else { (n2, n1) };
-            edges.insert(Edge { id: edge, ns: edge });
+            edges.push(Edge { id: edge, ns: edge });
         }
     }
-    let edges: HashMap<usize, Edge> = edges.into_iter().enumerate().collect();
+    edges.sort_by_key(|edge| edge.id);
+    edges.dedup();
+    let edges: BTreeMap<usize, Edge> = edges.into_iter().enumerate().collect();
 
     let mut nodes: HashMap<&str, HashSet<usize>> = HashMap::new();
     for (idx, edge) in &edges {
@@ -27,13 +32,15 @@ fn parse(input: &str) -> (HashMap<&str, HashSet<usize>>, HashMap<usize, Edge>) {
         nodes.entry(edge.id.1).or_default().insert(*idx);
     }
 
-    (nodes, edges)
+    Ok((nodes, edges))
 }
 
-pub fn part1(input: &str) -> crate::Result<usize> {
-    let (nodes, edges) = parse(input);
-
-    let mut rand = fs::File::open("/dev/urandom").unwrap();
+/// Splits the graph in two by cutting three edges, found by contracting
+/// randomly chosen edges, and multiplies the sizes of the two groups.
+fn split((nodes, edges): &Graph, rng: &mut Rng) -> crate::Result<usize> {
+    if nodes.len() < 2 {
+        return Err(crate::Error::invalid("there is nothing to split"));
+    }
     let mut score: HashMap<(&str, &str), usize> = HashMap::new();
     let mut cut = HashSet::new();
     while cut.len() < 3 {
@@ -41,14 +48,12 @@ pub fn part1(input: &str) -> crate::Result<usize> {
         let mut edges = edges.clone();
 
         while nodes.len() > 2 {
-            let idx = {
-                let mut bytes = [0u8; 8];
-                rand.read_exact(&mut bytes).unwrap();
-                *edges
-                    .keys()
-                    .nth(usize::from_le_bytes(bytes) % edges.len())
-                    .unwrap()
-            };
+            if edges.is_empty() {
+                return Err(crate::Error::invalid(
+                    "the components are not all connected",
+                ));
+            }
+            let idx = *edges.keys().nth(rng.below(edges.len())).unwrap();
 
             let (kept, removed) = edges[&idx].ns;
             for edge_idx in nodes.remove(removed).unwrap() {
@@ -74,6 +79,18 @@ pub fn part1(input: &str) -> crate::Result<usize> {
             }
         }
 
+        // a contraction that leaves just three edges has found the cut, and
+        // one that leaves fewer has found a cut that is too small
+        if edges.len() < 3 {
+            return Err(crate::Error::invalid(
+                "the components can be split by cutting fewer than three wires",
+            ));
+        }
+        if edges.len() == 3 {
+            cut = edges.values().map(|edge| edge.id).collect();
+            break;
+        }
+
         for edge in edges.values() {
             let score = score.entry(edge.id).or_default();
             *score += 1;
@@ -84,14 +101,14 @@ pub fn part1(input: &str) -> crate::Result<usize> {
     }
 
     let graph: HashMap<&str, HashSet<&str>> = nodes
-        .into_iter()
-        .map(|(id, edge_indexes)| {
+        .iter()
+        .map(|(&id, edge_indexes)| {
             let mut node_edges: HashSet<&str> = HashSet::new();
             node_edges.extend(
                 edge_indexes
-                    .into_iter()
-                    .filter(|idx| !cut.contains(&edges[&idx].id))
-                    .flat_map(|idx| [edges[&idx].id.0, edges[&idx].id.1])
+                    .iter()
+                    .filter(|idx| !cut.contains(&edges[idx].id))
+                    .flat_map(|idx| [edges[idx].id.0, edges[idx].id.1])
                     .filter(|n| *n != id),
             );
             (id, node_edges)
@@ -111,3 +128,25 @@ pub fn part1(input: &str) -> crate::Result<usize> {
 
     Ok(seen.len() * (graph.len() - seen.len()))
 }
+
+pub fn part1(graph: &Graph) -> crate::Result<usize> {
+    split(graph, &mut Rng::from_seed())
+}
+
+pub const SOLUTION: crate::Solution = solution!(25, "Snowverload", parse, [part1]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_split_seeds() {
+        let input = fs::read_to_string("examples/day25/ex1.txt").unwrap();
+        let input = crate::input::normalize(&input);
+        let graph = parse(&input).unwrap();
+        for seed in 0..10 {
+            assert_eq!(54, split(&graph, &mut Rng::new(seed)).unwrap());
+        }
+    }
+}
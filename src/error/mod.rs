@@ -0,0 +1,206 @@
+use std::{
+    any::{self, Any},
+    error, fmt, result,
+    str::FromStr,
+};
+
+pub type Result<T> = result::Result<T, Error>;
+
+/// 1-based position in the input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum Error {
+    /// Input that doesn't have the expected format
+    Parse {
+        day: Option<u32>,
+        position: Option<Position>,
+        /// The offending text
+        text: String,
+        /// Address of the offending text, used to locate it in the input
+        addr: usize,
+        expected: String,
+    },
+    /// Well-formed input that the solution can't handle
+    Invalid {
+        day: Option<u32>,
+        msg: String,
+    },
+    Usage,
+    /// A step took longer than the `--timeout`
+    Timeout,
+    /// A step panicked, which is a bug in the solution
+    Panic {
+        msg: String,
+    },
+    /// Some parts didn't produce the expected answer
+    Verification {
+        failed: usize,
+    },
+    /// Some steps got slower between two runs
+    Regression {
+        regressed: usize,
+    },
+    /// Some mutated inputs made a solution panic
+    Panics {
+        panics: usize,
+    },
+    /// Some days timed out on most mutated inputs, so they were hardly checked
+    Unchecked {
+        days: usize,
+    },
+}
+
+impl Error {
+    /// Reports that `text` is not what was `expected`.
+    ///
+    /// `text` should be a slice of the puzzle input, so that [`Error::locate`]
+    /// can find its position. When what's missing has no text of its own,
+    /// pass the enclosing slice, e.g. the whole line.
+    pub fn parse(text: &str, expected: impl Into<String>) -> Self {
+        Error::Parse {
+            day: None,
+            position: None,
+            text: text.to_string(),
+            addr: text.as_ptr() as usize,
+            expected: expected.into(),
+        }
+    }
+
+    pub fn invalid(msg: impl Into<String>) -> Self {
+        Error::Invalid {
+            day: None,
+            msg: msg.into(),
+        }
+    }
+
+    /// Turns the payload of a caught panic into an error.
+    pub fn panic(payload: Box<dyn Any + Send>) -> Self {
+        let msg = match payload.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(payload) => match payload.downcast::<&str>() {
+                Ok(msg) => msg.to_string(),
+                Err(_) => "unknown panic".to_string(),
+            },
+        };
+        Error::Panic { msg }
+    }
+
+    /// Attributes the error to `day` and, for parse errors whose text is a
+    /// slice of `input`, resolves the text's line and column.
+    pub fn locate(mut self, day: u32, input: &str) -> Self {
+        match &mut self {
+            Error::Parse {
+                day: d,
+                position,
+                addr,
+                ..
+            } => {
+                *d = Some(day);
+                let start = input.as_ptr() as usize;
+                if (start..=start + input.len()).contains(addr) {
+                    let before = &input[..*addr - start];
+                    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+                    *position = Some(Position {
+                        line: 1 + before.matches('\n').count(),
+                        column: 1 + before[line_start..].chars().count(),
+                    });
+                }
+            }
+            Error::Invalid { day: d, .. } => *d = Some(day),
+            _ => (),
+        }
+        self
+    }
+}
+
+/// Parses `s` as a `T`, reporting where the input was malformed on failure.
+pub fn parse<T: FromStr>(s: &str) -> Result<T> {
+    s.parse()
+        .map_err(|_| Error::parse(s, any::type_name::<T>()))
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse {
+                day,
+                position,
+                text,
+                expected,
+                ..
+            } => {
+                if let Some(day) = day {
+                    write!(f, "day {}, ", day)?;
+                }
+                if let Some(Position { line, column }) = position {
+                    write!(f, "line {}, column {}: ", line, column)?;
+                }
+                if text.is_empty() {
+                    write!(f, "expected {}, found nothing", expected)
+                } else {
+                    write!(f, "expected {}, found '{}'", expected, text)
+                }
+            }
+            Error::Invalid { day, msg } => {
+                if let Some(day) = day {
+                    write!(f, "day {}: ", day)?;
+                }
+                write!(f, "{}", msg)
+            }
+            Error::Usage => write!(f, "invalid usage"),
+            Error::Timeout => write!(f, "TIMEOUT"),
+            Error::Panic { msg } => write!(f, "panicked: {}", msg),
+            Error::Verification { failed } => write!(f, "{} part(s) failed", failed),
+            Error::Regression { regressed } => write!(f, "{} step(s) regressed", regressed),
+            Error::Panics { panics } => write!(f, "{} input(s) caused a panic", panics),
+            Error::Unchecked { days } => write!(f, "{} day(s) mostly timed out", days),
+        }
+    }
+}
+
+/// Same as [`fmt::Display`], so that errors returned from `main` are readable
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate() {
+        let input = "Game 1: 3 blue\nGame 2: 4 bleu, 2 red\n";
+        let line = input.lines().nth(1).unwrap();
+        let err = Error::parse(&line[10..14], "a color").locate(2, input);
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 11: expected a color, found 'bleu'"
+        );
+
+        assert_eq!(parse::<u32>(&line[5..6]).unwrap(), 2);
+        let err = parse::<u32>(&line[..4]).unwrap_err().locate(2, input);
+        assert_eq!(
+            err.to_string(),
+            "day 2, line 2, column 1: expected u32, found 'Game'"
+        );
+
+        // text that isn't part of the input can't be located
+        let err = Error::parse("elsewhere", "a color").locate(2, input);
+        assert_eq!(
+            err.to_string(),
+            "day 2, expected a color, found 'elsewhere'"
+        );
+
+        let err = Error::invalid("no solution").locate(7, input);
+        assert_eq!(err.to_string(), "day 7: no solution");
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{rng::Rng, Params, Solution};
+use std::{
+    io::{Read, Write},
+    ops::Range,
+    panic::{self, AssertUnwindSafe},
+    process::{Command, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread, time,
+};
+
+/// Characters that are likely to confuse a parser, on top of those already
+/// in the input. The non-ASCII ones catch solutions that slice lines at byte
+/// offsets.
+const SPECIAL: &str = "0123456789-+,.:;=#() \né\u{feff}";
+
+/// Numbers that overflow the integer types solutions are likely to parse
+/// into, or the arithmetic they do with them.
+const LARGE: [&str; 5] = [
+    "4294967295",
+    "4294967296",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "18446744073709551615",
+];
+
+/// Positions of the runs of characters in `chars` that match `pred`
+fn runs(chars: &[char], pred: impl Fn(char) -> bool) -> Vec<Range<usize>> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for (i, &c) in chars.iter().chain(['\n'].iter()).enumerate() {
+        match (start, pred(c) && i < chars.len()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                runs.push(s..i);
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    runs
+}
+
+/// Makes a few random edits to `input`: replacing, inserting, deleting or
+/// duplicating characters, dropping or swapping lines, replacing a number
+/// with a large one, swapping two numbers on a line (which reverses ranges)
+/// and renaming a name to another one (which makes things refer to
+/// themselves).
+fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let pool: Vec<char> = chars.iter().copied().chain(SPECIAL.chars()).collect();
+    for _ in 0..1 + rng.below(3) {
+        let len = chars.len();
+        let at = rng.below(len + 1);
+        let end = (at + 1 + rng.below(8)).min(len);
+        match rng.below(9) {
+            0 if at < len => chars[at] = pool[rng.below(pool.len())],
+            1 => chars.insert(at, pool[rng.below(pool.len())]),
+            2 => {
+                chars.drain(at..end);
+            }
+            3 => {
+                let span = chars[at..end].to_vec();
+                chars.splice(at..at, span);
+            }
+            4 => chars.truncate(at),
+            5 => {
+                let numbers = runs(&chars, |c| c.is_ascii_digit());
+                if !numbers.is_empty() {
+                    let number = numbers[rng.below(numbers.len())].clone();
+                    let large = LARGE[rng.below(LARGE.len())];
+                    chars.splice(number, large.chars());
+                }
+            }
+            6 => {
+                let numbers = runs(&chars, |c| c.is_ascii_digit());
+                if !numbers.is_empty() {
+                    let i = rng.below(numbers.len());
+                    let a = numbers[i].clone();
+                    let same_line = numbers[i + 1..]
+                        .iter()
+                        .take_while(|b| !chars[a.end..b.start].contains(&'\n'))
+                        .count();
+                    if same_line > 0 {
+                        let b = numbers[i + 1 + rng.below(same_line)].clone();
+                        let mut swapped = chars[..a.start].to_vec();
+                        swapped.extend_from_slice(&chars[b.clone()]);
+                        swapped.extend_from_slice(&chars[a.end..b.start]);
+                        swapped.extend_from_slice(&chars[a]);
+                        swapped.extend_from_slice(&chars[b.end..]);
+                        chars = swapped;
+                    }
+                }
+            }
+            7 => {
+                let names = runs(&chars, char::is_alphanumeric);
+                if !names.is_empty() {
+                    let name = names[rng.below(names.len())].clone();
+                    let other = chars[names[rng.below(names.len())].clone()].to_vec();
+                    chars.splice(name, other);
+                }
+            }
+            _ => {
+                let text: String = chars.iter().collect();
+                let mut lines: Vec<&str> = text.lines().collect();
+                if !lines.is_empty() {
+                    let (a, b) = (rng.below(lines.len()), rng.below(lines.len()));
+                    if rng.below(2) == 0 {
+                        lines.swap(a, b);
+                    } else {
+                        lines.remove(a);
+                    }
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// How a solution handled a mutated input
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// Every step returned, whether with an answer or an error
+    Handled,
+    /// A step took longer than the timeout
+    Timeout,
+    /// A step panicked or crashed, as described by the message
+    Panic(String),
+}
+
+/// Parses `input` with `params` and, if that succeeds, solves `parts` on a
+/// new thread, which sends an [`Error::Panic`](crate::Error::Panic) if a
+/// step panics.
+///
+/// The thread gets a large stack, as mutations can make the solutions that
+/// recurse go much deeper than on real inputs, and overflowing the stack
+/// aborts the whole process.
+fn spawn(
+    solution: &'static Solution,
+    input: String,
+    params: Params,
+    parts: Vec<usize>,
+) -> mpsc::Receiver<crate::Result<()>> {
+    let (tx, rx) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(256 << 20)
+        .spawn(move || {
+            let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                if let Ok(parsed) = (solution.parse)(&crate::input::normalize(&input), &params) {
+                    for part in parts {
+                        let _ = parsed.solve(part);
+                    }
+                }
+            }));
+            let _ = tx.send(res.map_err(crate::Error::panic));
+        })
+        .expect("failed to spawn a fuzzing thread");
+    rx
+}
+
+/// Parses `input` with `params` and, if that succeeds, solves `parts`.
+///
+/// The steps run on a thread of this process, which is abandoned if they
+/// take longer than `timeout`, since a mutated input can easily make a
+/// solution loop forever. The thread keeps running in the background, so
+/// [`check_in_child`] is better suited to many inputs that may time out.
+pub fn check(
+    solution: &'static Solution,
+    input: String,
+    params: Params,
+    parts: Vec<usize>,
+    timeout: time::Duration,
+) -> Outcome {
+    match spawn(solution, input, params, parts).recv_timeout(timeout) {
+        Ok(Ok(())) => Outcome::Handled,
+        Ok(Err(e)) => Outcome::Panic(e.to_string()),
+        Err(RecvTimeoutError::Timeout) => Outcome::Timeout,
+        Err(RecvTimeoutError::Disconnected) => Outcome::Panic("unknown panic".to_string()),
+    }
+}
+
+/// Parses `input` with `params` and, if that succeeds, solves `parts`,
+/// waiting for as long as that takes.
+///
+/// This is what the child processes of [`check_in_child`] run. Fails with
+/// [`Error::Panic`](crate::Error::Panic) if a step panicked.
+pub fn run(
+    solution: &'static Solution,
+    input: String,
+    params: Params,
+    parts: Vec<usize>,
+) -> crate::Result<()> {
+    spawn(solution, input, params, parts)
+        .recv()
+        .unwrap_or_else(|_| {
+            Err(crate::Error::Panic {
+                msg: "unknown panic".to_string(),
+            })
+        })
+}
+
+/// Like [`check`], but runs `command` in a child process that is killed if it
+/// takes longer than `timeout`, so that nothing of it is left running.
+///
+/// The command is passed `input` on stdin and should [`run`] it, printing the
+/// panic message to stdout and failing if a step panics. A child that fails
+/// without a message crashed, e.g. by overflowing its stack.
+pub fn check_in_child(mut command: Command, input: &str, timeout: time::Duration) -> Outcome {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .expect("failed to spawn a fuzzing process");
+    if let Some(mut stdin) = child.stdin.take() {
+        // the child may have died before reading it all, which its exit
+        // status tells
+        let _ = stdin.write_all(input.as_bytes());
+    }
+
+    // the child's stdout closes when it exits, however that happens
+    let mut stdout = child.stdout.take().expect("the child's stdout is piped");
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut msg = String::new();
+        let _ = stdout.read_to_string(&mut msg);
+        let _ = tx.send(msg);
+    });
+    let msg = match rx.recv_timeout(timeout) {
+        Ok(msg) => msg,
+        Err(_) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Outcome::Timeout;
+        }
+    };
+    match child.wait() {
+        Ok(status) if status.success() => Outcome::Handled,
+        _ if !msg.trim().is_empty() => Outcome::Panic(msg.trim().to_string()),
+        Ok(status) => Outcome::Panic(format!("the process crashed, {}", status)),
+        Err(e) => Outcome::Panic(format!("the process was lost: {}", e)),
+    }
+}
+
+/// Mutates the input of a day for run number `run`, the same way every time.
+pub fn input(day: u32, run: usize, input: &str) -> String {
+    let mut rng = Rng::new(((day as u64) << 32) | run as u64);
+    mutate(input, &mut rng)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input_path, SOLUTIONS};
+    use std::fs;
+
+    #[test]
+    fn mutate() {
+        let text = "467..114..\n...*......\n..35..633.\n";
+        assert_eq!(input(3, 7, text), input(3, 7, text));
+        let changed = (0..100).filter(|&run| input(3, run, text) != text);
+        assert!(changed.count() > 90);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_in_child() {
+        let sh = |script: &str| {
+            let mut command = std::process::Command::new("sh");
+            command.args(["-c", script]);
+            command
+        };
+        let timeout = time::Duration::from_secs(1);
+        assert_eq!(
+            super::check_in_child(sh("cat"), "x", timeout),
+            Outcome::Handled
+        );
+        assert_eq!(
+            super::check_in_child(sh("echo 'panicked: boom'; exit 1"), "", timeout),
+            Outcome::Panic("panicked: boom".to_string())
+        );
+        assert!(matches!(
+            super::check_in_child(sh("kill -9 $$"), "", timeout),
+            Outcome::Panic(msg) if msg.contains("crashed")
+        ));
+        let start = time::Instant::now();
+        let outcome = super::check_in_child(sh("sleep 10"), "", time::Duration::from_millis(100));
+        assert_eq!(outcome, Outcome::Timeout);
+        assert!(start.elapsed() < time::Duration::from_secs(5));
+    }
+
+    /// Parsers should reject malformed input rather than panic. Solvers are
+    /// fuzzed with the `fuzz` command instead, as they are too slow on the
+    /// real inputs to run here.
+    #[test]
+    fn parsers() {
+        let mut panics = Vec::new();
+        for solution in SOLUTIONS.iter() {
+            let Ok(text) = fs::read_to_string(input_path(solution.day)) else {
+                continue;
+            };
+            for run in 0..50 {
+                let mutated = input(solution.day, run, &text);
+                let timeout = time::Duration::from_secs(10);
+                let params = solution.params(&[]);
+                if let Outcome::Panic(msg) = check(solution, mutated, params, Vec::new(), timeout) {
+                    panics.push(format!("day {}, run {}: {}", solution.day, run, msg));
+                }
+            }
+        }
+        assert!(panics.is_empty(), "{}", panics.join("\n"));
+    }
+
+    /// Unlike the mutations of real inputs, these are quick enough to solve,
+    /// so the parts are checked as well as the parsers.
+    #[test]
+    fn degenerate() {
+        let inputs = [
+            "",
+            "x",
+            "#",
+            ".",
+            "1",
+            "1 2",
+            "S",
+            "a: b",
+            "#.\n.#",
+            "é# 1",
+            "\u{feff}1abc2",
+            "-9223372036854775807 9223372036854775807",
+            "3,1,5~1,1,5",
+            "4294967295 4294967295\n4294967295 4294967295",
+            "Time: 18446744073709551615\nDistance: 9223372036854775807",
+            "seeds: 9 1\n\nmap:\n5 9223372036854775807 18446744073709551615",
+            "L\n\nAAA = (AAA, AAA)",
+            "in{x<5:in,A}\n\n{x=1,m=2,a=3,s=4}",
+            "broadcaster -> a\n&a -> a, rx",
+        ];
+        // inputs this small should be handled quickly, so a timeout is a hang
+        let mut failures = Vec::new();
+        for solution in SOLUTIONS.iter() {
+            let parts: Vec<usize> = solution.selected(None).collect();
+            for input in inputs {
+                let params = solution.params(&[]);
+                let timeout = time::Duration::from_secs(3);
+                let outcome = check(solution, input.to_string(), params, parts.clone(), timeout);
+                let failure = match outcome {
+                    Outcome::Handled => continue,
+                    Outcome::Timeout => "timed out".to_string(),
+                    Outcome::Panic(msg) => msg,
+                };
+                failures.push(format!("day {}, {:?}: {}", solution.day, input, failure));
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::report::{self, Record};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    fs::OpenOptions,
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::Duration,
+};
+
+/// A timed step of a day: parsing its input or solving one of its parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Step {
+    Parse,
+    Part(usize),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Parse => f.pad("parse"),
+            Step::Part(part) => f.pad(&part.to_string()),
+        }
+    }
+}
+
+impl FromStr for Step {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            "parse" => Ok(Step::Parse),
+            _ => crate::error::parse(s).map(Step::Part),
+        }
+    }
+}
+
+/// The time a step took in a run recorded under `label`
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub label: String,
+    pub day: u32,
+    pub step: Step,
+    pub time: Duration,
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.label,
+            self.day,
+            self.step,
+            self.time.as_nanos()
+        )
+    }
+}
+
+/// Timings of earlier runs, read from a file of
+/// `<label> <day> <step> <nanoseconds>` lines
+#[derive(Debug, Default)]
+pub struct History(Vec<Entry>);
+
+impl FromStr for History {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let mut entries = Vec::new();
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let &[label, day, step, nanos] = &fields[..] else {
+                return Err(crate::Error::parse(line, "'<label> <day> <step> <ns>'"));
+            };
+            entries.push(Entry {
+                label: label.to_string(),
+                day: crate::error::parse(day)?,
+                step: step.parse()?,
+                time: Duration::from_nanos(crate::error::parse(nanos)?),
+            });
+        }
+        Ok(History(entries))
+    }
+}
+
+impl History {
+    /// The fastest time of each step recorded under `label`, since slower
+    /// runs are mostly noise from whatever else the machine was doing
+    pub fn times(&self, label: &str) -> HashMap<(u32, Step), Duration> {
+        let mut times = HashMap::new();
+        for entry in self.0.iter().filter(|entry| entry.label == label) {
+            times
+                .entry((entry.day, entry.step))
+                .and_modify(|time: &mut Duration| *time = (*time).min(entry.time))
+                .or_insert(entry.time);
+        }
+        times
+    }
+}
+
+/// Entries for the steps of a run that succeeded.
+pub fn entries(label: &str, records: &[Record]) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    for record in records.iter().filter(|record| record.answer.is_ok()) {
+        let entry = |step, time| Entry {
+            label: label.to_string(),
+            day: record.day,
+            step,
+            time,
+        };
+        if !entries.iter().any(|e: &Entry| e.day == record.day) {
+            entries.push(entry(Step::Parse, record.parse));
+        }
+        entries.push(entry(Step::Part(record.part), record.solve));
+    }
+    entries
+}
+
+/// Appends `entries` to the history file at `path`, creating it if needed.
+pub fn append(path: &str, entries: &[Entry]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        writeln!(file, "{}", entry)?;
+    }
+    Ok(())
+}
+
+/// Labels a run with the current git commit, marked as dirty if there are
+/// uncommitted changes.
+pub fn git_label() -> Option<String> {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").args(args).output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let commit = git(&["rev-parse", "--short", "HEAD"])?;
+    let status = git(&["status", "--porcelain", "--untracked-files=no"])?;
+    if status.is_empty() {
+        Some(commit)
+    } else {
+        Some(format!("{}-dirty", commit))
+    }
+}
+
+/// How the time of a step changed between two labels
+#[derive(Debug, PartialEq)]
+pub struct Delta {
+    pub day: u32,
+    pub step: Step,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Delta {
+    /// Relative change in time, e.g. `0.1` for 10% slower
+    pub fn change(&self) -> f64 {
+        self.after.as_secs_f64() / self.before.as_secs_f64().max(1e-9) - 1.0
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.change() > threshold
+    }
+}
+
+/// Compares the steps that were timed under both labels, in day order.
+pub fn compare(history: &History, before: &str, after: &str) -> Vec<Delta> {
+    let after = history.times(after);
+    let deltas: BTreeMap<_, _> = history
+        .times(before)
+        .into_iter()
+        .filter_map(|(key, before)| Some((key, (before, *after.get(&key)?))))
+        .collect();
+    deltas
+        .into_iter()
+        .map(|((day, step), (before, after))| Delta {
+            day,
+            step,
+            before,
+            after,
+        })
+        .collect()
+}
+
+pub fn header(before: &str, after: &str) -> String {
+    format!(
+        "{:>3}  {:>5}  {:>16}  {:>16}  {:>8}",
+        "Day", "Part", before, after, "Change"
+    )
+}
+
+pub fn row(delta: &Delta, threshold: f64) -> String {
+    let row = format!(
+        "{:>3}  {:>5}  {:>16}  {:>16}  {:>+7.1}%",
+        delta.day,
+        delta.step,
+        report::format_time(delta.before),
+        report::format_time(delta.after),
+        100.0 * delta.change()
+    );
+    if delta.regressed(threshold) {
+        row + "  regressed"
+    } else {
+        row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::tests::record;
+
+    #[test]
+    fn entries() {
+        let records = [
+            record(1, 1, Ok("54953")),
+            record(1, 2, Ok("53868")),
+            record(2, 1, Err("expected a digit")),
+        ];
+        let entries = super::entries("abc123", &records);
+        let lines: Vec<String> = entries.iter().map(Entry::to_string).collect();
+        assert_eq!(
+            lines,
+            ["abc123 1 parse 567", "abc123 1 1 1234", "abc123 1 2 1234"]
+        );
+
+        let history: History = (lines.join("\n") + "\n").parse().unwrap();
+        assert_eq!(history.0, entries);
+        assert!("abc123 1 parse".parse::<History>().is_err());
+        assert!("abc123 1 three 100".parse::<History>().is_err());
+    }
+
+    #[test]
+    fn compare() {
+        let history: History = "a 1 parse 100
+            a 1 1 1000
+            a 1 1 800
+            a 2 1 500
+            b 1 parse 100
+            b 1 1 1000
+            b 3 1 500"
+            .parse()
+            .unwrap();
+        let deltas = super::compare(&history, "a", "b");
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[0].step, Step::Parse);
+        assert_eq!(deltas[0].change(), 0.0);
+        assert_eq!(deltas[1].step, Step::Part(1));
+        assert_eq!(deltas[1].before, Duration::from_nanos(800));
+        assert!(deltas[1].regressed(0.2));
+        assert!(!deltas[1].regressed(0.3));
+    }
+}
@@ -0,0 +1,59 @@
+/// Separates the sections of normalised input
+const SECTION_BREAK: &str = "\n\n";
+
+/// Brings `input` into the form every day's parser expects: `\n` line
+/// endings, no trailing whitespace on any line, sections separated by a
+/// single blank line, and no blank lines at the start or end.
+///
+/// Line numbers in errors refer to the normalised input, which only differs
+/// from the original where it had blank lines at the start or several blank
+/// lines in a row.
+pub fn normalize(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len());
+    let mut blank = false;
+    // `lines` drops the `\r` of `\r\n` endings, and `trim_end` any stray ones
+    for line in input.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank = !normalized.is_empty();
+            continue;
+        }
+        if !normalized.is_empty() {
+            normalized.push('\n');
+            if blank {
+                normalized.push('\n');
+                blank = false;
+            }
+        }
+        normalized.push_str(line);
+    }
+    normalized
+}
+
+/// Splits normalised input into its sections.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input.split(SECTION_BREAK)
+}
+
+/// Splits normalised input into its first section and the rest, or `None`
+/// if there is only one section.
+pub fn split_section(input: &str) -> Option<(&str, &str)> {
+    input.split_once(SECTION_BREAK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        let input = "\r\n seeds: 79 14 \r\n\r\n \t\r\n\nseed-to-soil map:\r\n50 98 2\t\r\n\r\n";
+        let normalized = super::normalize(input);
+        assert_eq!(normalized, " seeds: 79 14\n\nseed-to-soil map:\n50 98 2");
+        assert_eq!(
+            sections(&normalized).collect::<Vec<_>>(),
+            [" seeds: 79 14", "seed-to-soil map:\n50 98 2"]
+        );
+        assert_eq!(super::normalize(&normalized), normalized);
+        assert_eq!(split_section("LR"), None);
+    }
+}
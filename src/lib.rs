@@ -0,0 +1,316 @@
+//! Solutions to Advent of Code 2023, with a runner to solve, benchmark and
+//! verify them.
+
+/// Builds a [`Solution`] from a day's `parse` function and its part
+/// functions, each of which takes a reference to the parsed input.
+///
+/// A day with [`Param`]s declares them with their defaults after its parts,
+/// as in `params[steps = 64]`. Its parts that use them are marked as in
+/// `[part1(params), part2]`, and also take the [`Params`].
+macro_rules! solution {
+    (@part $part:ident (params)) => {
+        |input, params| $part(input, params)
+    };
+    (@part $part:ident) => {
+        |input, _| $part(input)
+    };
+    ($day:expr, $name:expr, $parse:path, [$($part:path),+]) => {
+        solution!(@ $day, $name, $parse, [$(|input, _| $part(input)),+], [])
+    };
+    ($day:expr, $name:expr, $parse:path, [$($part:ident $(($with:tt))?),+],
+        params [$($param:ident = $default:expr),+ $(,)?]) => {
+        solution!(
+            @ $day, $name, $parse,
+            [$(solution!(@part $part $(($with))?)),+],
+            [$($param = $default),+]
+        )
+    };
+    (@ $day:expr, $name:expr, $parse:path, [$($part:expr),+],
+        [$($param:ident = $default:expr),*]) => {
+        $crate::Solution {
+            day: $day,
+            name: $name,
+            parts: [$(stringify!($part)),+].len(),
+            params: &[$($crate::Param { name: stringify!($param), default: $default }),*],
+            parse: |input, params| {
+                let parsed: Box<dyn $crate::Solve> = Box::new($crate::Parsed {
+                    input: $parse(input)?,
+                    params: params.clone(),
+                    parts: [$(|input, params| ($part)(input, params).map($crate::Answer::from)),+],
+                });
+                Ok(parsed)
+            },
+        }
+    };
+}
+
+/// Writes a diagnostic to stderr, prefixed with the day, when the runner was
+/// given at least `$level` `-v` flags: 1 for a summary of what a part found,
+/// 2 for its intermediate steps.
+macro_rules! trace {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level) {
+            eprintln!(
+                "[{}] {}",
+                $crate::trace::source(module_path!()),
+                format_args!($($arg)+)
+            );
+        }
+    };
+}
+
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+pub mod error;
+pub mod fuzz;
+pub mod history;
+pub mod input;
+pub mod params;
+pub mod report;
+pub mod rng;
+pub mod runner;
+pub mod scaffold;
+pub mod trace;
+
+pub use answer::Answer;
+pub use error::{Error, Result};
+pub use params::{Param, Params};
+
+/// A day's input after parsing, ready to solve any of its parts
+pub trait Solve {
+    /// Solves part `part`, numbered from 1.
+    fn solve(&self, part: usize) -> Result<Answer>;
+}
+
+/// A day's parsed input together with its part functions and the values of
+/// its parameters
+struct Parsed<T, const N: usize> {
+    input: T,
+    params: Params,
+    parts: [fn(&T, &Params) -> Result<Answer>; N],
+}
+
+impl<T, const N: usize> Solve for Parsed<T, N> {
+    fn solve(&self, part: usize) -> Result<Answer> {
+        (self.parts[part - 1])(&self.input, &self.params)
+    }
+}
+
+/// Parses a day's input into something that can solve its parts with the
+/// given parameters.
+///
+/// The input must have been normalised with [`input::normalize`], as the
+/// runner does: the parsers, like each day's `parse`, may reject or misread
+/// input with `\r\n` line endings or stray blank lines.
+pub type Parse = for<'a> fn(&'a str, &Params) -> Result<Box<dyn Solve + 'a>>;
+
+/// A day's puzzle, as registered in [`SOLUTIONS`]
+#[derive(Debug)]
+pub struct Solution {
+    pub day: u32,
+    pub name: &'static str,
+    /// Number of parts
+    pub parts: usize,
+    pub params: &'static [Param],
+    /// Parses normalised input, see [`Parse`]
+    pub parse: Parse,
+}
+
+pub const SOLUTIONS: [Solution; 25] = [
+    day01::SOLUTION,
+    day02::SOLUTION,
+    day03::SOLUTION,
+    day04::SOLUTION,
+    day05::SOLUTION,
+    day06::SOLUTION,
+    day07::SOLUTION,
+    day08::SOLUTION,
+    day09::SOLUTION,
+    day10::SOLUTION,
+    day11::SOLUTION,
+    day12::SOLUTION,
+    day13::SOLUTION,
+    day14::SOLUTION,
+    day15::SOLUTION,
+    day16::SOLUTION,
+    day17::SOLUTION,
+    day18::SOLUTION,
+    day19::SOLUTION,
+    day20::SOLUTION,
+    day21::SOLUTION,
+    day22::SOLUTION,
+    day23::SOLUTION,
+    day24::SOLUTION,
+    day25::SOLUTION,
+];
+
+impl Solution {
+    /// Iterates over the parts selected by `part`, numbered from 1.
+    pub fn selected(&self, part: Option<usize>) -> impl Iterator<Item = usize> {
+        (1..=self.parts).filter(move |num| part.is_none_or(|part| part == *num))
+    }
+
+    /// The values of the day's parameters, where those not in `values`, or
+    /// only given for other days, keep their defaults.
+    pub fn params(&self, values: &[(String, i64)]) -> Params {
+        Params::new(self.day, self.params, values)
+    }
+}
+
+pub fn solution(day: u32) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|s| s.day == day)
+}
+
+/// Parses a day selection such as `all`, `5`, `1-10` or `3,7,19`.
+pub fn parse_days(s: &str) -> Option<Vec<&'static Solution>> {
+    if s == "all" {
+        return Some(SOLUTIONS.iter().collect());
+    }
+
+    let mut days = Vec::new();
+    for item in s.split(',') {
+        let (from, to) = if let Some((from, to)) = item.split_once('-') {
+            (from.parse().ok()?, to.parse().ok()?)
+        } else {
+            let day = item.parse().ok()?;
+            (day, day)
+        };
+        if from > to {
+            return None;
+        }
+        for day in from..=to {
+            days.push(solution(day)?);
+        }
+    }
+    Some(days)
+}
+
+/// Default input file of a day
+pub fn input_path(day: u32) -> String {
+    format!("input/day{:02}", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    fn solve(
+        day: u32,
+        path: &str,
+        part: usize,
+        params: &[(&str, i64)],
+    ) -> crate::Result<crate::Answer> {
+        let solution = crate::solution(day).unwrap();
+        let values: Vec<(String, i64)> = params
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        let inp = crate::input::normalize(&fs::read_to_string(path).unwrap());
+        let parsed = (solution.parse)(&inp, &solution.params(&values)).unwrap();
+        parsed.solve(part)
+    }
+
+    fn answer(day: u32, path: &str, part: usize, params: &[(&str, i64)]) -> crate::Answer {
+        solve(day, path, part, params).unwrap()
+    }
+
+    fn days(s: &str) -> Option<Vec<u32>> {
+        crate::parse_days(s).map(|days| days.into_iter().map(|s| s.day).collect())
+    }
+
+    #[test]
+    fn parse_days() {
+        assert_eq!(days("all"), Some((1..=25).collect()));
+        assert_eq!(days("5"), Some(vec![5]));
+        assert_eq!(days("1-3"), Some(vec![1, 2, 3]));
+        assert_eq!(days("3,7,19"), Some(vec![3, 7, 19]));
+        assert_eq!(days("1-2,24-25"), Some(vec![1, 2, 24, 25]));
+        assert_eq!(days("0"), None);
+        assert_eq!(days("26"), None);
+        assert_eq!(days("3-1"), None);
+        assert_eq!(days("x"), None);
+    }
+
+    /// The parts of `days` that have no answer in the repository at `root`,
+    /// other than those of days whose input is empty, as `aoc2023 new` leaves
+    /// it until the day is solved
+    pub(crate) fn missing_answers(root: &Path, days: &[(u32, usize)]) -> Vec<(u32, usize)> {
+        let answers: crate::answers::Answers = fs::read_to_string(root.join("answers"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        days.iter()
+            .filter(|(day, _)| {
+                let input = fs::metadata(root.join(crate::input_path(*day)));
+                !input.is_ok_and(|meta| meta.len() == 0)
+            })
+            .flat_map(|&(day, parts)| (1..=parts).map(move |part| (day, part)))
+            .filter(|&(day, part)| answers.get(day, part).is_none())
+            .collect()
+    }
+
+    #[test]
+    fn registry() {
+        for (idx, solution) in crate::SOLUTIONS.iter().enumerate() {
+            assert_eq!(solution.day as usize, idx + 1);
+            assert!(solution.parts > 0);
+        }
+        let days: Vec<(u32, usize)> = crate::SOLUTIONS
+            .iter()
+            .map(|solution| (solution.day, solution.parts))
+            .collect();
+        assert_eq!(missing_answers(Path::new("."), &days), []);
+    }
+
+    /// Parameters far beyond the puzzle's make the answers overflow, which
+    /// should be reported rather than panic or wrap around.
+    #[test]
+    fn overflowing_params() {
+        let invalid = |day, part, param| {
+            let path = format!("examples/day{:02}/ex1.txt", day);
+            let res = solve(day, &path, part, &[param]);
+            assert!(
+                matches!(res, Err(crate::Error::Invalid { .. })),
+                "day {}: {:?}",
+                day,
+                res
+            );
+        };
+        invalid(11, 2, ("expansion2", i64::MAX));
+        invalid(19, 2, ("max", 100_000_000));
+    }
+
+    fn check(day: u32, path: &str, part: usize, expected: &str, params: &[(&str, i64)]) {
+        assert_eq!(answer(day, path, part, params), expected.parse().unwrap());
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/answers.rs"));
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -1,511 +1,504 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-use std::{env, error, fmt, fs, result, time};
-
-#[derive(Debug)]
-struct UsageError;
-
-type Result<T> = result::Result<T, Box<dyn error::Error>>;
-
-#[derive(Debug)]
-struct Error<T> {
-    err: T,
+use aoc2023::{
+    answers::Answers,
+    history::{self, History},
+    input_path, parse_days,
+    report::{Format, Record},
+    runner::{self, Options, Source},
+    scaffold, Error, Result, SOLUTIONS,
+};
+use std::{env, fs, path::Path, result, time};
+
+#[cfg(feature = "count-alloc")]
+#[global_allocator]
+static ALLOC: aoc2023::alloc::Counting = aoc2023::alloc::Counting;
+
+/// Parses the value following an option.
+fn value<T: std::str::FromStr>(
+    args: &mut impl Iterator<Item = String>,
+    name: &str,
+) -> result::Result<T, String> {
+    let value = args
+        .next()
+        .ok_or_else(|| format!("Missing value for '{}'", name))?;
+    value
+        .parse()
+        .map_err(|_| format!("Could not parse {}: '{}'", name, value))
 }
 
-impl<T> Error<T> {
-    fn boxed(err: T) -> Box<Self> {
-        Box::new(Self { err })
+/// Splits the command line into positional arguments and options.
+fn parse_args(
+    mut args: impl Iterator<Item = String>,
+) -> result::Result<(Vec<String>, Options), String> {
+    let mut positional = Vec::new();
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => match value(&mut args, &arg)? {
+                0 => return Err("Parts are numbered from 1".to_string()),
+                part => options.part = Some(part),
+            },
+            "--format" => options.format = value(&mut args, &arg)?,
+            "--runs" => match value(&mut args, &arg)? {
+                0 => return Err("Need at least one run".to_string()),
+                runs => options.runs = Some(runs),
+            },
+            "--budget" => {
+                let secs = value(&mut args, &arg)?;
+                options.budget = time::Duration::try_from_secs_f64(secs)
+                    .map_err(|_| format!("Invalid budget: '{}'", secs))?;
+            }
+            "--iterations" => match value(&mut args, &arg)? {
+                0 => return Err("Need at least one iteration".to_string()),
+                iterations => options.iterations = Some(iterations),
+            },
+            "--duration" => {
+                let secs = value(&mut args, &arg)?;
+                options.duration = time::Duration::try_from_secs_f64(secs)
+                    .map_err(|_| format!("Invalid duration: '{}'", secs))?;
+            }
+            "--warmup" => options.warmup = value(&mut args, &arg)?,
+            "--timeout" => {
+                let secs = value(&mut args, &arg)?;
+                match time::Duration::try_from_secs_f64(secs) {
+                    Ok(timeout) if !timeout.is_zero() => options.timeout = Some(timeout),
+                    _ => return Err(format!("Invalid timeout: '{}'", secs)),
+                }
+            }
+            "-v" => options.verbosity = 1,
+            "-vv" => options.verbosity = 2,
+            "--param" => options
+                .params
+                .push(aoc2023::params::parse(&value::<String>(&mut args, &arg)?)?),
+            "--seed" => options.seed = Some(value(&mut args, &arg)?),
+            "--answers" => options.answers = value(&mut args, &arg)?,
+            "--jobs" => match value(&mut args, &arg)? {
+                0 => return Err("Need at least one job".to_string()),
+                jobs => options.jobs = jobs,
+            },
+            "--history" => options.history = value(&mut args, &arg)?,
+            "--label" => {
+                let label: String = value(&mut args, &arg)?;
+                if label.is_empty() || label.contains(char::is_whitespace) {
+                    return Err(format!("Invalid label: '{}'", label));
+                }
+                options.label = Some(label);
+            }
+            "--threshold" => {
+                let percent: f64 = value(&mut args, &arg)?;
+                if percent.is_nan() || percent < 0.0 {
+                    return Err(format!("Invalid threshold: '{}'", percent));
+                }
+                options.threshold = percent / 100.0;
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("Unknown option: '{}'", arg))
+            }
+            _ => positional.push(arg),
+        }
     }
+    Ok((positional, options))
 }
 
-impl<T: fmt::Debug> fmt::Display for Error<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self.err)
-    }
+fn usage() -> Result<()> {
+    eprintln!("usage: aoc2023 [<options>] <day> [<input>]");
+    eprintln!("       aoc2023 [<options>] all|<days> [<dir>]");
+    eprintln!("       aoc2023 [<options>] bench <day> [<input>]|all|<days>");
+    eprintln!("       aoc2023 [<options>] verify [<day> [<input>]|all|<days>]");
+    eprintln!("       aoc2023 [<options>] compare <label> <label>");
+    eprintln!("       aoc2023 [<options>] fuzz <day>|all|<days>");
+    eprintln!("       aoc2023 [<options>] watch <day> [<input>]");
+    eprintln!("       aoc2023 [<options>] profile <day> [<input>]");
+    eprintln!("       aoc2023 new <day> [<name>]");
+    eprintln!();
+    eprintln!("<input> defaults to 'input/dayNN', or stdin if it is piped in or redirected");
+    eprintln!("from a file. Use '-' to read stdin explicitly.");
+    eprintln!();
+    eprintln!("<days> is a list of days and ranges, e.g. '1-10' or '3,7,19'");
+    eprintln!();
+    eprintln!("<dir> has a directory of inputs per account, e.g. 'inputs/<account>/dayNN'.");
+    eprintln!("Each day is run against every account, and the answers and timings are");
+    eprintln!("shown side by side.");
+    eprintln!();
+    eprintln!("The timings of runs on the default inputs are added to a history file,");
+    eprintln!("labelled with the git commit. 'compare' shows how they changed between");
+    eprintln!("two labels.");
+    eprintln!();
+    eprintln!("'fuzz' solves randomly mutated inputs and reports those that panic, or");
+    eprintln!("that time out and are left unchecked.");
+    eprintln!("'watch' solves a day again whenever its input or examples change.");
+    eprintln!("'profile' solves a part over and over, for a profiler such as perf to");
+    eprintln!("sample. It needs --part unless the day has a single part.");
+    eprintln!("'new' sets up a module, input and examples for a day that isn't solved yet.");
+    eprintln!();
+    eprintln!("options:");
+    eprintln!("    -v, -vv            trace what the days find to stderr, -vv in more detail");
+    eprintln!("    --part <part>      only run the given part");
+    eprintln!("    --jobs <n>         run up to <n> days at the same time, at most one per");
+    eprintln!("                       CPU (default 1)");
+    eprintln!("    --format <format>  output format: text (default), json or csv");
+    eprintln!("    --timeout <secs>   give up on a part after <secs> and report TIMEOUT");
+    eprintln!("    --param [<day>.]<name>=<value>");
+    eprintln!("                       change a constant of the selected days' puzzles,");
+    eprintln!("                       e.g. --param steps1=6 or --param 21.steps1=6");
+    eprintln!("    --seed <n>         seed for days that make random choices (default: from");
+    eprintln!("                       the OS, and printed)");
+    eprintln!("    --runs <n>         bench: time each part <n> times");
+    eprintln!("                       fuzz: mutated inputs per day (default 100)");
+    eprintln!("    --budget <secs>    bench: time each part for <secs> (default 1)");
+    eprintln!("    --warmup <n>       bench: untimed runs before timing (default 1)");
+    eprintln!("    --iterations <n>   profile: solve the part <n> times");
+    eprintln!("    --duration <secs>  profile: solve the part for <secs> (default 10)");
+    eprintln!("    --answers <file>   verify: expected answers (default 'answers')");
+    eprintln!("    --history <file>   timing history (default 'history')");
+    eprintln!("    --label <label>    label timings with <label> instead of the git commit");
+    eprintln!("    --threshold <pct>  compare: slowdown that counts as a regression");
+    eprintln!("                       (default 10)");
+    Err(Error::Usage)
 }
 
-impl<T: fmt::Debug> error::Error for Error<T> {
-    fn cause(&self) -> Option<&dyn error::Error> {
-        Some(self)
+/// Adds the timings of a run to the history file.
+fn record(records: &[Record], options: &Options) {
+    // bench, verify and the other commands have nothing to record
+    if records.is_empty() {
+        return;
+    }
+    let Some(label) = options.label.clone().or_else(history::git_label) else {
+        eprintln!("Not recording timings, use --label outside of a git repository");
+        return;
+    };
+    let entries = history::entries(&label, records);
+    if let Err(e) = history::append(&options.history, &entries) {
+        eprintln!("Could not record timings in '{}': {}", options.history, e);
     }
 }
 
-fn print_time(d: time::Duration) {
-    println!(
-        "> {}.{:03} {:03} {:03} seconds",
-        d.as_secs(),
-        d.subsec_millis(),
-        d.subsec_micros() % 1_000,
-        d.subsec_nanos() % 1_000,
-    );
-}
-
-fn time<F: Fn(A) -> B, A, B>(f: F, a: A) -> B {
-    let now = time::Instant::now();
-    let res = f(a);
-    let d = now.elapsed();
-    print_time(d);
-    res
-}
+/// Prints how the timings recorded under two labels compare.
+fn compare(mut labels: impl Iterator<Item = String>, options: &Options) -> Result<()> {
+    let (Some(before), Some(after), None) = (labels.next(), labels.next(), labels.next()) else {
+        eprintln!("'compare' takes two labels");
+        return usage();
+    };
+    let history: History = match fs::read_to_string(&options.history) {
+        Ok(history) => history.parse()?,
+        Err(_) => {
+            eprintln!("No such file: '{}'", options.history);
+            return usage();
+        }
+    };
+    for label in [&before, &after] {
+        if history.times(label).is_empty() {
+            return Err(Error::invalid(format!("No timings for '{}'", label)));
+        }
+    }
 
-fn usage() -> Result<()> {
-    eprintln!("usage: aoc2023 <day> [<input>]");
-    Err(Error::boxed(UsageError {}))
+    println!("{}", history::header(&before, &after));
+    let mut regressed = 0;
+    for delta in history::compare(&history, &before, &after) {
+        if delta.regressed(options.threshold) {
+            regressed += 1;
+        }
+        println!("{}", history::row(&delta, options.threshold));
+    }
+    if regressed > 0 {
+        return Err(Error::Regression { regressed });
+    }
+    Ok(())
 }
 
 fn main() -> Result<()> {
-    let (day, input) = {
-        let mut args = env::args().skip(1);
-        let d = if let Some(d) = args.next() {
-            if let Ok(d) = d.parse() {
-                d
-            } else {
-                eprintln!("Could not parse day: '{}'", d);
-                return usage();
-            }
-        } else {
-            eprintln!("Not enough arguments");
+    let (args, options) = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            return usage();
+        }
+    };
+    aoc2023::trace::set_level(options.verbosity);
+    if let Some(seed) = options.seed {
+        aoc2023::rng::set_seed(seed);
+    }
+
+    let mut args = args.into_iter().peekable();
+    let command = args.next_if(|arg| {
+        [
+            "bench",
+            "verify",
+            "compare",
+            "fuzz",
+            "fuzz-case",
+            "watch",
+            "profile",
+            "new",
+        ]
+        .contains(&arg.as_str())
+    });
+    if let Some(command) = command.as_ref().filter(|_| options.format != Format::Text) {
+        eprintln!("'{}' only supports text output", command);
+        return usage();
+    }
+
+    if command.as_deref() == Some("compare") {
+        return compare(args, &options);
+    }
+
+    if command.as_deref() == Some("new") {
+        let Some(day) = args
+            .next()
+            .and_then(|d| d.parse().ok())
+            .filter(|d| (1..=25).contains(d))
+        else {
+            eprintln!("'new' takes a day from 1 to 25");
             return usage();
         };
+        let name = args.next().unwrap_or_else(|| format!("Day {}", day));
+        for file in scaffold::new_day(day, &name)? {
+            println!("Wrote {}", file);
+        }
+        return Ok(());
+    }
 
-        let i = args.next().unwrap_or_else(|| format!("input/day{:02}", d));
-        let i = if let Ok(i) = fs::read_to_string(&i) {
-            i
-        } else {
-            eprintln!("No such file: '{}'", &i);
+    // run by 'fuzz' for each mutated input, not meant to be run by hand
+    if command.as_deref() == Some("fuzz-case") {
+        let Some(solution) = args
+            .next()
+            .and_then(|d| d.parse().ok())
+            .and_then(aoc2023::solution)
+        else {
             return usage();
         };
+        return runner::fuzz_case(solution, &options);
+    }
 
-        (d, i)
+    let days = if let Some(d) = args.next() {
+        if let Some(days) = parse_days(&d) {
+            days
+        } else {
+            eprintln!("Could not parse day: '{}'", d);
+            return usage();
+        }
+    } else if command.as_deref() == Some("verify") {
+        SOLUTIONS.iter().collect()
+    } else {
+        eprintln!("Not enough arguments");
+        return usage();
     };
 
-    match day {
-        1 => {
-            println!("Part 1: {}", time(day01::part1, input.trim())?);
-            println!("Part 2: {}", time(day01::part2, input.trim())?);
-        }
-        2 => {
-            println!("Part 1: {}", time(day02::part1, input.trim())?);
-            println!("Part 2: {}", time(day02::part2, input.trim())?);
-        }
-        3 => {
-            println!("Part 1: {}", time(day03::part1, input.trim())?);
-            println!("Part 2: {}", time(day03::part2, input.trim())?);
-        }
-        4 => {
-            println!("Part 1: {}", time(day04::part1, input.trim())?);
-            println!("Part 2: {}", time(day04::part2, input.trim())?);
-        }
-        5 => {
-            println!("Part 1: {}", time(day05::part1, input.trim())?);
-            println!("Part 2: {}", time(day05::part2, input.trim())?);
-        }
-        6 => {
-            println!("Part 1: {}", time(day06::part1, input.trim())?);
-            println!("Part 2: {}", time(day06::part2, input.trim())?);
-        }
-        7 => {
-            println!("Part 1: {}", time(day07::part1, input.trim())?);
-            println!("Part 2: {}", time(day07::part2, input.trim())?);
-        }
-        8 => {
-            println!("Part 1: {}", time(day08::part1, input.trim())?);
-            println!("Part 2: {}", time(day08::part2, input.trim())?);
-        }
-        9 => {
-            println!("Part 1: {}", time(day09::part1, input.trim())?);
-            println!("Part 2: {}", time(day09::part2, input.trim())?);
-        }
-        10 => {
-            println!("Part 1: {}", time(day10::part1, input.trim())?);
-            println!("Part 2: {}", time(day10::part2, input.trim())?);
-        }
-        11 => {
-            println!("Part 1: {}", time(day11::part1, input.trim())?);
-            println!("Part 2: {}", time(day11::part2, input.trim())?);
-        }
-        12 => {
-            println!("Part 1: {}", time(day12::part1, input.trim())?);
-            println!("Part 2: {}", time(day12::part2, input.trim())?);
-        }
-        13 => {
-            println!("Part 1: {}", time(day13::part1, input.trim())?);
-            println!("Part 2: {}", time(day13::part2, input.trim())?);
-        }
-        14 => {
-            println!("Part 1: {}", time(day14::part1, input.trim())?);
-            println!("Part 2: {}", time(day14::part2, input.trim())?);
-        }
-        15 => {
-            println!("Part 1: {}", time(day15::part1, input.trim())?);
-            println!("Part 2: {}", time(day15::part2, input.trim())?);
+    for (name, _) in &options.params {
+        let (scope, param) = aoc2023::params::scope(name);
+        let declaring: Vec<u32> = days
+            .iter()
+            .filter(|s| scope.is_none_or(|day| day == s.day))
+            .filter(|s| s.params.iter().any(|p| p.name == param))
+            .map(|s| s.day)
+            .collect();
+        if declaring.len() > 1 {
+            let list: Vec<String> = declaring.iter().map(u32::to_string).collect();
+            eprintln!("Days {} all have a parameter '{}'", list.join(", "), name);
+            eprintln!(
+                "Scope it to one of them, e.g. --param {}.{}=<value>",
+                declaring[0], name
+            );
+            return usage();
         }
-        16 => {
-            println!("Part 1: {}", time(day16::part1, input.trim())?);
-            println!("Part 2: {}", time(day16::part2, input.trim())?);
+        if declaring.is_empty() {
+            eprintln!("No selected day has a parameter '{}'", name);
+            for solution in days.iter().filter(|s| !s.params.is_empty()) {
+                let params: Vec<String> = solution
+                    .params
+                    .iter()
+                    .map(|p| format!("{}={}", p.name, p.default))
+                    .collect();
+                eprintln!("Day {} has {}", solution.day, params.join(" "));
+            }
+            return usage();
         }
-        17 => {
-            println!("Part 1: {}", time(day17::part1, input.trim())?);
-            println!("Part 2: {}", time(day17::part2, input.trim())?);
+    }
+
+    if command.as_deref() == Some("watch") {
+        let [solution] = days[..] else {
+            eprintln!("'watch' takes a single day");
+            return usage();
+        };
+        let input = args.next().unwrap_or_else(|| input_path(solution.day));
+        if input == "-" {
+            eprintln!("'watch' needs an input file");
+            return usage();
         }
-        18 => {
-            println!("Part 1: {}", time(day18::part1, input.trim())?);
-            println!("Part 2: {}", time(day18::part2, input.trim())?);
+        runner::watch_day(solution, &input, &options);
+    }
+
+    if command.as_deref() == Some("profile") {
+        let [solution] = days[..] else {
+            eprintln!("'profile' takes a single day");
+            return usage();
+        };
+        match options.part {
+            None if solution.parts > 1 => {
+                eprintln!("'profile' needs --part for day {}", solution.day);
+                return usage();
+            }
+            Some(part) if part > solution.parts => {
+                eprintln!("Day {} has no part {}", solution.day, part);
+                return usage();
+            }
+            _ => (),
         }
-        19 => {
-            println!("Part 1: {}", time(day19::part1, input.trim())?);
-            println!("Part 2: {}", time(day19::part2, input.trim())?);
+    }
+
+    let single = days.len() == 1;
+    let input = args.next();
+    if let Some(dir) = input.as_deref().filter(|path| Path::new(path).is_dir()) {
+        if command.is_some() || options.format != Format::Text {
+            eprintln!("A directory of inputs can only be run, with text output");
+            return usage();
         }
-        20 => {
-            println!("Part 1: {}", time(day20::part1, input.trim())?);
-            println!("Part 2: {}", time(day20::part2, input.trim())?);
+        return match runner::run_matrix(&days, dir, &options) {
+            Ok(0) => Ok(()),
+            Ok(failed) => Err(Error::Verification { failed }),
+            Err(msg) => {
+                eprintln!("{}", msg);
+                usage()
+            }
+        };
+    }
+
+    let (days, source) = match runner::read_inputs(days, input, &options) {
+        Ok(inputs) => inputs,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            return usage();
         }
-        21 => {
-            println!("Part 1: {}", time(day21::part1, input.trim())?);
-            println!("Part 2: {}", time(day21::part2, input.trim())?);
+    };
+
+    let records = match command.as_deref() {
+        Some("bench") => {
+            runner::bench_days(&days, &options);
+            Vec::new()
         }
-        22 => {
-            println!("Part 1: {}", time(day22::part1, input.trim())?);
-            println!("Part 2: {}", time(day22::part2, input.trim())?);
+        Some("verify") => {
+            let answers: Answers = match fs::read_to_string(&options.answers) {
+                Ok(answers) => answers.parse()?,
+                Err(_) => {
+                    eprintln!("No such file: '{}'", options.answers);
+                    return usage();
+                }
+            };
+            let failed = runner::verify_days(&days, &answers, &options);
+            if failed > 0 {
+                eprintln!("{} part(s) failed", failed);
+                return Err(Error::Verification { failed });
+            }
+            Vec::new()
         }
-        23 => {
-            println!("Part 1: {}", time(day23::part1, input.trim())?);
-            println!("Part 2: {}", time(day23::part2, input.trim())?);
+        Some("profile") => {
+            let (solution, input) = &days[0];
+            runner::profile_day(solution, input, options.part.unwrap_or(1), &options)?;
+            Vec::new()
         }
-        24 => {
-            println!("Part 1: {}", time(day24::part1, input.trim())?);
-            println!("Part 2: {}", time(day24::part2, input.trim())?);
+        Some("fuzz") => {
+            runner::fuzz_days(&days, &options)?;
+            Vec::new()
         }
-        25 => {
-            println!("Part 1: {}", time(day25::part1, input.trim())?);
+        _ if single && options.format == Format::Text => {
+            let (solution, input) = &days[0];
+            runner::run_day(solution, input, &options)?
         }
-        _ => unimplemented!(),
+        _ => runner::run_days(&days, &options),
+    };
+
+    // timings are only comparable between runs on the same input and
+    // parameters
+    if source == Source::Default && options.params.is_empty() {
+        record(&records, &options);
     }
 
+    // like a single day, a run of several fails if any part timed out or
+    // failed, after reporting all of them
+    let failed = records.iter().filter(|r| r.answer.is_err()).count();
+    if failed > 0 {
+        return Err(Error::Verification { failed });
+    }
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-
-    #[test]
-    fn day01p1() {
-        let inp = include_str!("../input/day01");
-        assert_eq!(crate::day01::part1(inp.trim()).unwrap(), 54953);
-    }
-
-    #[test]
-    fn day01p2() {
-        let inp = include_str!("../input/day01");
-        assert_eq!(crate::day01::part2(inp.trim()).unwrap(), 53868);
-    }
-
-    #[test]
-    fn day02p1() {
-        let inp = include_str!("../input/day02");
-        assert_eq!(crate::day02::part1(inp.trim()).unwrap(), 2563);
-    }
-
-    #[test]
-    fn day02p2() {
-        let inp = include_str!("../input/day02");
-        assert_eq!(crate::day02::part2(inp.trim()).unwrap(), 70768);
-    }
-
-    #[test]
-    fn day03p1() {
-        let inp = include_str!("../input/day03");
-        assert_eq!(crate::day03::part1(inp.trim()).unwrap(), 554003);
-    }
-
-    #[test]
-    fn day03p2() {
-        let inp = include_str!("../input/day03");
-        assert_eq!(crate::day03::part2(inp.trim()).unwrap(), 87263515);
-    }
-
-    #[test]
-    fn day04p1() {
-        let inp = include_str!("../input/day04");
-        assert_eq!(crate::day04::part1(inp.trim()).unwrap(), 20107);
-    }
-
-    #[test]
-    fn day04p2() {
-        let inp = include_str!("../input/day04");
-        assert_eq!(crate::day04::part2(inp.trim()).unwrap(), 8172507);
-    }
-
-    #[test]
-    fn day05p1() {
-        let inp = include_str!("../input/day05");
-        assert_eq!(crate::day05::part1(inp.trim()).unwrap(), 157211394);
-    }
-
-    #[test]
-    fn day05p2() {
-        let inp = include_str!("../input/day05");
-        assert_eq!(crate::day05::part2(inp.trim()).unwrap(), 50855035);
-    }
-
-    #[test]
-    fn day06p1() {
-        let inp = include_str!("../input/day06");
-        assert_eq!(crate::day06::part1(inp.trim()).unwrap(), 281600);
-    }
-
-    #[test]
-    fn day06p2() {
-        let inp = include_str!("../input/day06");
-        assert_eq!(crate::day06::part2(inp.trim()).unwrap(), 33875953);
-    }
-
-    #[test]
-    fn day07p1() {
-        let inp = include_str!("../input/day07");
-        assert_eq!(crate::day07::part1(inp.trim()).unwrap(), 251216224);
-    }
-
-    #[test]
-    fn day07p2() {
-        let inp = include_str!("../input/day07");
-        assert_eq!(crate::day07::part2(inp.trim()).unwrap(), 250825971);
-    }
-
-    #[test]
-    fn day08p1() {
-        let inp = include_str!("../input/day08");
-        assert_eq!(crate::day08::part1(inp.trim()).unwrap(), 12083);
-    }
-
-    #[test]
-    fn day08p2() {
-        let inp = include_str!("../input/day08");
-        assert_eq!(crate::day08::part2(inp.trim()).unwrap(), 13385272668829);
-    }
-
-    #[test]
-    fn day09p1() {
-        let inp = include_str!("../input/day09");
-        assert_eq!(crate::day09::part1(inp.trim()).unwrap(), 1939607039);
-    }
-
-    #[test]
-    fn day09p2() {
-        let inp = include_str!("../input/day09");
-        assert_eq!(crate::day09::part2(inp.trim()).unwrap(), 1041);
-    }
-
-    #[test]
-    fn day10p1() {
-        let inp = include_str!("../input/day10");
-        assert_eq!(crate::day10::part1(inp.trim()).unwrap(), 6942);
-    }
-
-    #[test]
-    fn day10p2() {
-        let inp = include_str!("../input/day10");
-        assert_eq!(crate::day10::part2(inp.trim()).unwrap(), 297);
-    }
-
-    #[test]
-    fn day11p1() {
-        let inp = include_str!("../input/day11");
-        assert_eq!(crate::day11::part1(inp.trim()).unwrap(), 10494813);
-    }
-
-    #[test]
-    fn day11p2() {
-        let inp = include_str!("../input/day11");
-        assert_eq!(crate::day11::part2(inp.trim()).unwrap(), 840988812853);
-    }
-
-    #[test]
-    fn day12p1() {
-        let inp = include_str!("../input/day12");
-        assert_eq!(crate::day12::part1(inp.trim()).unwrap(), 7407);
-    }
-
-    #[test]
-    fn day12p2() {
-        let inp = include_str!("../input/day12");
-        assert_eq!(crate::day12::part2(inp.trim()).unwrap(), 30568243604962);
-    }
-
-    #[test]
-    fn day13p1() {
-        let inp = include_str!("../input/day13");
-        assert_eq!(crate::day13::part1(inp.trim()).unwrap(), 42974);
-    }
-
-    #[test]
-    fn day13p2() {
-        let inp = include_str!("../input/day13");
-        assert_eq!(crate::day13::part2(inp.trim()).unwrap(), 27587);
-    }
-
-    #[test]
-    fn day14p1() {
-        let inp = include_str!("../input/day14");
-        assert_eq!(crate::day14::part1(inp.trim()).unwrap(), 110677);
-    }
-
-    #[test]
-    fn day14p2() {
-        let inp = include_str!("../input/day14");
-        assert_eq!(crate::day14::part2(inp.trim()).unwrap(), 90551);
-    }
-
-    #[test]
-    fn day15p1() {
-        let inp = include_str!("../input/day15");
-        assert_eq!(crate::day15::part1(inp.trim()).unwrap(), 510273);
-    }
-
-    #[test]
-    fn day15p2() {
-        let inp = include_str!("../input/day15");
-        assert_eq!(crate::day15::part2(inp.trim()).unwrap(), 212449);
-    }
-
-    #[test]
-    fn day16p1() {
-        let inp = include_str!("../input/day16");
-        assert_eq!(crate::day16::part1(inp.trim()).unwrap(), 6361);
-    }
-
-    #[test]
-    fn day16p2() {
-        let inp = include_str!("../input/day16");
-        assert_eq!(crate::day16::part2(inp.trim()).unwrap(), 6701);
-    }
-
-    #[test]
-    fn day17p1() {
-        let inp = include_str!("../input/day17");
-        assert_eq!(crate::day17::part1(inp.trim()).unwrap(), 843);
-    }
-
-    #[test]
-    fn day17p2() {
-        let inp = include_str!("../input/day17");
-        assert_eq!(crate::day17::part2(inp.trim()).unwrap(), 1017);
-    }
-
-    #[test]
-    fn day18p1() {
-        let inp = include_str!("../input/day18");
-        assert_eq!(crate::day18::part1(inp.trim()).unwrap(), 108909);
-    }
-
-    #[test]
-    fn day18p2() {
-        let inp = include_str!("../input/day18");
-        assert_eq!(crate::day18::part2(inp.trim()).unwrap(), 133125706867777);
-    }
-
-    #[test]
-    fn day19p1() {
-        let inp = include_str!("../input/day19");
-        assert_eq!(crate::day19::part1(inp.trim()).unwrap(), 353553);
-    }
-
-    #[test]
-    fn day19p2() {
-        let inp = include_str!("../input/day19");
-        assert_eq!(crate::day19::part2(inp.trim()).unwrap(), 124615747767410);
-    }
-
-    #[test]
-    fn day20p1() {
-        let inp = include_str!("../input/day20");
-        assert_eq!(crate::day20::part1(inp.trim()).unwrap(), 896998430);
-    }
-
     #[test]
-    fn day20p2() {
-        let inp = include_str!("../input/day20");
-        assert_eq!(crate::day20::part2(inp.trim()).unwrap(), 236095992539963);
-    }
-
-    #[test]
-    fn day21p1() {
-        let inp = include_str!("../input/day21");
-        assert_eq!(crate::day21::part1(inp.trim()).unwrap(), 3598);
-    }
-
-    #[test]
-    fn day21p2() {
-        let inp = include_str!("../input/day21");
-        assert_eq!(crate::day21::part2(inp.trim()).unwrap(), 601441063166538);
-    }
-
-    #[test]
-    fn day22p1() {
-        let inp = include_str!("../input/day22");
-        assert_eq!(crate::day22::part1(inp.trim()).unwrap(), 495);
-    }
-
-    #[test]
-    fn day22p2() {
-        let inp = include_str!("../input/day22");
-        assert_eq!(crate::day22::part2(inp.trim()).unwrap(), 76158);
-    }
-
-    #[test]
-    fn day23p1() {
-        let inp = include_str!("../input/day23");
-        assert_eq!(crate::day23::part1(inp.trim()).unwrap(), 2042);
-    }
-
-    #[test]
-    fn day23p2() {
-        let inp = include_str!("../input/day23");
-        assert_eq!(crate::day23::part2(inp.trim()).unwrap(), 6466);
-    }
-
-    #[test]
-    fn day24p1() {
-        let inp = include_str!("../input/day24");
-        assert_eq!(crate::day24::part1(inp.trim()).unwrap(), 31208);
-    }
-
-    #[test]
-    fn day24p2() {
-        let inp = include_str!("../input/day24");
-        assert_eq!(crate::day24::part2(inp.trim()).unwrap(), 580043851566574);
-    }
-
-    #[test]
-    fn day25p1() {
-        let inp = include_str!("../input/day25");
-        assert_eq!(crate::day25::part1(inp.trim()).unwrap(), 602151);
+    fn parse_args() {
+        let args = |s: &str| crate::parse_args(s.split_whitespace().map(String::from));
+
+        let (positional, options) = args("17 --part 2 input/day17").unwrap();
+        assert_eq!(positional, ["17", "input/day17"]);
+        assert_eq!(options.part, Some(2));
+        assert_eq!(options.format, crate::Format::Text);
+
+        let (_, options) = args("all --format csv").unwrap();
+        assert_eq!(options.format, crate::Format::Csv);
+
+        let (positional, options) = args("all").unwrap();
+        assert_eq!(positional, ["all"]);
+        assert_eq!(options.part, None);
+
+        assert!(args("1 --part").is_err());
+        assert!(args("1 --part 0").is_err());
+        assert!(args("1 --part two").is_err());
+
+        let (positional, options) = args("bench 6 --runs 100 --warmup 5").unwrap();
+        assert_eq!(positional, ["bench", "6"]);
+        assert_eq!(options.runs, Some(100));
+        assert_eq!(options.warmup, 5);
+
+        let (_, options) = args("bench all --budget 0.5").unwrap();
+        assert_eq!(options.runs, None);
+        assert_eq!(options.budget, std::time::Duration::from_millis(500));
+
+        let (_, options) = args("all --jobs 4").unwrap();
+        assert_eq!(options.jobs, 4);
+
+        assert!(args("all --jobs 0").is_err());
+
+        let (_, options) = args("all --timeout 2.5").unwrap();
+        assert_eq!(
+            options.timeout,
+            Some(std::time::Duration::from_millis(2500))
+        );
+        assert!(args("all --timeout 0").is_err());
+
+        let (positional, options) = args("8 -vv -").unwrap();
+        assert_eq!(positional, ["8", "-"]);
+        assert_eq!(options.verbosity, 2);
+        assert!(args("8 -vvv").is_err());
+        assert!(args("8 -x").is_err());
+
+        let (_, options) = args("25 --seed 42").unwrap();
+        assert_eq!(options.seed, Some(42));
+        assert!(args("25 --seed -1").is_err());
+
+        let (positional, options) = args("profile 12 --part 2 --iterations 50").unwrap();
+        assert_eq!(positional, ["profile", "12"]);
+        assert_eq!(options.iterations, Some(50));
+        assert!(args("profile 12 --iterations 0").is_err());
+
+        let (_, options) = args("21 --param steps1=6 --param steps2=10").unwrap();
+        assert_eq!(
+            options.params,
+            [("steps1".to_string(), 6), ("steps2".to_string(), 10)]
+        );
+        assert!(args("21 --param steps1").is_err());
+        let (_, options) = args("19,24 --param 24.max=27").unwrap();
+        assert_eq!(options.params, [("24.max".to_string(), 27)]);
+        assert!(args("24 --param day24.max=27").is_err());
+
+        let (_, options) = args("profile 12 --duration 2").unwrap();
+        assert_eq!(options.duration, std::time::Duration::from_secs(2));
+
+        let (_, options) = args("compare a b --threshold 5 --history h").unwrap();
+        assert_eq!(options.threshold, 0.05);
+        assert_eq!(options.history, "h");
+        assert!(args("compare a b --threshold -5").is_err());
+        assert!(args("bench 6 --runs 0").is_err());
+        assert!(args("bench 6 --budget -1").is_err());
+        assert!(args("1 --format yaml").is_err());
+        assert!(args("1 --frobnicate").is_err());
     }
 }
-
-// vim macro to prepare new day..
-// gg0}kyyp/unimplky3k3jp/daynG{ky2{Pzt7nnnnnnG0
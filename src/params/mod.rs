@@ -0,0 +1,125 @@
+use crate::{Error, Result};
+
+/// A constant of a day's puzzle that can be changed with `--param`, e.g. the
+/// number of steps to take
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+}
+
+/// The values of a day's parameters, as passed to its parts
+#[derive(Debug, Clone, PartialEq)]
+pub struct Params(Vec<(&'static str, i64)>);
+
+impl Params {
+    /// The defaults of the parameters `day` declares, overridden by those of
+    /// `values` that are declared and not scoped to another day. Other values
+    /// are left for other days.
+    pub fn new(day: u32, declared: &'static [Param], values: &[(String, i64)]) -> Self {
+        Params(
+            declared
+                .iter()
+                .map(|param| {
+                    let value = values
+                        .iter()
+                        .rfind(|(name, _)| {
+                            let (scope, name) = scope(name);
+                            scope.is_none_or(|scope| scope == day) && name == param.name
+                        })
+                        .map_or(param.default, |(_, value)| *value);
+                    (param.name, value)
+                })
+                .collect(),
+        )
+    }
+
+    /// The value of parameter `name`, which must have been declared, as the
+    /// type the part works with.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> Result<T> {
+        let value = self
+            .0
+            .iter()
+            .find(|(param, _)| *param == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("undeclared parameter '{}'", name));
+        T::try_from(value)
+            .map_err(|_| Error::invalid(format!("parameter {} is out of range: {}", name, value)))
+    }
+}
+
+/// Splits a parameter name such as `24.max` into the day it is scoped to, if
+/// any, and the name the day declares.
+pub fn scope(name: &str) -> (Option<u32>, &str) {
+    match name.split_once('.') {
+        Some((day, param)) => (day.parse().ok(), param),
+        None => (None, name),
+    }
+}
+
+/// Parses a `--param` value such as `steps=500`, or `21.steps=500` to only
+/// change it for day 21.
+pub fn parse(s: &str) -> std::result::Result<(String, i64), String> {
+    let (name, value) = s
+        .split_once('=')
+        .filter(|(name, _)| !name.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "Invalid parameter, expected '[<day>.]<name>=<value>': '{}'",
+                s
+            )
+        })?;
+    if let Some((day, param)) = name.split_once('.') {
+        if day.parse::<u32>().is_err() || param.is_empty() {
+            return Err(format!(
+                "Invalid parameter name, expected '[<day>.]<name>': '{}'",
+                name
+            ));
+        }
+    }
+    let value = value
+        .parse()
+        .map_err(|_| format!("Invalid value for parameter {}: '{}'", name, value))?;
+    Ok((name.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[
+        Param {
+            name: "steps",
+            default: 64,
+        },
+        Param {
+            name: "min",
+            default: 1,
+        },
+    ];
+
+    #[test]
+    fn params() {
+        let params = Params::new(21, DECLARED, &[]);
+        assert_eq!(params.get::<usize>("steps").unwrap(), 64);
+
+        let values = [parse("min=-3").unwrap(), parse("cycles=7").unwrap()];
+        let params = Params::new(21, DECLARED, &values);
+        assert_eq!(params.get::<i64>("min").unwrap(), -3);
+        assert!(params.get::<usize>("min").is_err());
+        assert_eq!(params.get::<u8>("steps").unwrap(), 64);
+
+        let values = [parse("21.steps=6").unwrap(), parse("19.min=5").unwrap()];
+        let params = Params::new(21, DECLARED, &values);
+        assert_eq!(params.get::<usize>("steps").unwrap(), 6);
+        assert_eq!(params.get::<i64>("min").unwrap(), 1);
+        assert_eq!(scope("21.steps"), (Some(21), "steps"));
+        assert_eq!(scope("steps"), (None, "steps"));
+
+        assert!(parse("steps").is_err());
+        assert!(parse("x.steps=6").is_err());
+        assert!(parse("21.=6").is_err());
+        assert!(parse("=5").is_err());
+        assert!(parse("steps=x").is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{alloc, Answer};
+use std::{str::FromStr, time::Duration};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format: '{}'", s)),
+        }
+    }
+}
+
+/// The outcome of running a single part
+#[derive(Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: usize,
+    pub answer: Result<Answer, String>,
+    /// Time spent parsing the day's input, shared by all its parts
+    pub parse: Duration,
+    pub solve: Duration,
+    /// What solving allocated, if allocations are counted
+    pub allocs: Option<alloc::Stats>,
+}
+
+pub fn format_time(d: Duration) -> String {
+    format!(
+        "{}.{:03} {:03} {:03}",
+        d.as_secs(),
+        d.subsec_millis(),
+        d.subsec_micros() % 1_000,
+        d.subsec_nanos() % 1_000,
+    )
+}
+
+/// Formats the time a step took, followed by what it allocated if known.
+pub fn format_cost(d: Duration, allocs: Option<&alloc::Stats>) -> String {
+    match allocs {
+        Some(allocs) => format!("{:<14}  {}", format_time(d), allocs),
+        None => format_time(d),
+    }
+}
+
+pub fn table_header() -> String {
+    format!("{:>3}  {:>5}  {:<16}  Time", "Day", "Part", "Answer")
+}
+
+pub fn table_parse_row(day: u32, parse: Duration, allocs: Option<&alloc::Stats>) -> String {
+    format!(
+        "{:>3}  {:>5}  {:<16}  {}",
+        day,
+        "parse",
+        "",
+        format_cost(parse, allocs)
+    )
+}
+
+/// A row of the table, followed by the lines of a multi-line answer, which
+/// don't fit in its column.
+pub fn table_row(record: &Record) -> String {
+    let (answer, text) = match &record.answer {
+        Ok(Answer::Text(text)) => (format!("({} lines)", text.lines().count()), Some(text)),
+        Ok(answer) => (answer.to_string(), None),
+        Err(e) => (format!("error: {}", e), None),
+    };
+    let row = format!(
+        "{:>3}  {:>5}  {:<16}  {}",
+        record.day,
+        record.part,
+        answer,
+        format_cost(record.solve, record.allocs.as_ref())
+    );
+    match text {
+        Some(text) => row + "\n" + text,
+        None => row,
+    }
+}
+
+pub fn table_total(total: Duration) -> String {
+    format!("{:<28}  {}", "Total", format_time(total))
+}
+
+fn json_string(s: &str) -> String {
+    let mut res = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            c if c.is_control() => res.push_str(&format!("\\u{:04x}", c as u32)),
+            c => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+fn json_record(record: &Record) -> String {
+    let (answer, error) = match &record.answer {
+        Ok(answer) if answer.is_int() => (answer.to_string(), "null".to_string()),
+        Ok(answer) => (json_string(&answer.to_string()), "null".to_string()),
+        Err(e) => ("null".to_string(), json_string(e)),
+    };
+    format!(
+        "{{\"day\": {}, \"part\": {}, \"answer\": {}, \"error\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+        record.day,
+        record.part,
+        answer,
+        error,
+        record.parse.as_nanos(),
+        record.solve.as_nanos()
+    )
+}
+
+/// Renders all records as a JSON array, one record per line.
+pub fn json(records: &[Record]) -> String {
+    let records: Vec<String> = records
+        .iter()
+        .map(|r| format!("  {}", json_record(r)))
+        .collect();
+    if records.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n]", records.join(",\n"))
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn csv_header() -> &'static str {
+    "day,part,answer,error,parse_ns,solve_ns"
+}
+
+pub fn csv_row(record: &Record) -> String {
+    let (answer, error) = match &record.answer {
+        Ok(answer) => (csv_field(&answer.to_string()), String::new()),
+        Err(e) => (String::new(), csv_field(e)),
+    };
+    format!(
+        "{},{},{},{},{},{}",
+        record.day,
+        record.part,
+        answer,
+        error,
+        record.parse.as_nanos(),
+        record.solve.as_nanos()
+    )
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// A record of `part` of `day` with fixed timings
+    pub(crate) fn record(day: u32, part: usize, answer: Result<&str, &str>) -> Record {
+        Record {
+            day,
+            part,
+            answer: answer.map(|a| a.parse().unwrap()).map_err(String::from),
+            parse: Duration::from_nanos(567),
+            solve: Duration::from_nanos(1234),
+            allocs: None,
+        }
+    }
+
+    #[test]
+    fn json_output() {
+        assert_eq!(json(&[]), "[]");
+        assert_eq!(
+            json(&[
+                record(7, 2, Ok("42")),
+                record(7, 2, Ok("#.\\n.#")),
+                record(7, 2, Err("bad \"input\""))
+            ]),
+            "[
+  {\"day\": 7, \"part\": 2, \"answer\": 42, \"error\": null, \"parse_ns\": 567, \"solve_ns\": 1234},
+  {\"day\": 7, \"part\": 2, \"answer\": \"#.\\n.#\", \"error\": null, \"parse_ns\": 567, \"solve_ns\": 1234},
+  {\"day\": 7, \"part\": 2, \"answer\": null, \"error\": \"bad \\\"input\\\"\", \"parse_ns\": 567, \"solve_ns\": 1234}
+]"
+        );
+    }
+
+    #[test]
+    fn csv_output() {
+        assert_eq!(csv_row(&record(7, 2, Ok("42"))), "7,2,42,,567,1234");
+        assert_eq!(
+            csv_row(&record(7, 2, Ok("#.\\n.#"))),
+            "7,2,\"#.\n.#\",,567,1234"
+        );
+        assert_eq!(
+            csv_row(&record(7, 2, Err("bad, \"input\""))),
+            "7,2,,\"bad, \"\"input\"\"\",567,1234"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::OnceLock,
+};
+
+/// Seed of the solutions that make random choices, fixed for the whole run
+static SEED: OnceLock<u64> = OnceLock::new();
+
+/// Sets the seed that [`seed`] returns, as given by `--seed`. Has no effect
+/// once the seed has been used.
+pub fn set_seed(seed: u64) {
+    let _ = SEED.set(seed);
+}
+
+/// The seed of this run. Unless one was set, it is drawn from the OS the first
+/// time it is needed, and printed to stderr so the run can be replayed.
+pub fn seed() -> u64 {
+    *SEED.get_or_init(|| {
+        // the keys of a `RandomState` come from the OS
+        let seed = RandomState::new().build_hasher().finish();
+        eprintln!("Random seed {}, replay with --seed {}", seed, seed);
+        seed
+    })
+}
+
+/// Xorshift generator, so that random choices can be reproduced from a seed
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // splitmix64 maps every seed to a different state, except that
+        // xorshift never leaves zero, so the one seed that maps there gets
+        // another state of its own
+        let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        Rng(if z == 0 { 0x2545_f491_4f6c_dd1d } else { z })
+    }
+
+    /// A generator seeded with the [`seed`] of this run
+    pub fn from_seed() -> Self {
+        Rng::new(seed())
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `0..n`, where `n` must not be zero
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproducible() {
+        let draws = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.below(10)).collect::<Vec<_>>()
+        };
+        assert_eq!(draws(7), draws(7));
+        assert_ne!(draws(7), draws(8));
+        assert_ne!(draws(0), draws(1));
+        assert!(draws(0).iter().all(|&n| n < 10));
+        assert!((0..10).all(|n| draws(0).contains(&n)));
+    }
+}
@@ -0,0 +1,996 @@
+use crate::{
+    alloc,
+    answers::{Answers, Status},
+    bench, fuzz, input, input_path,
+    report::{self, Format, Record},
+    rng, Answer, Error, Params, Result, Solution,
+};
+use std::{
+    collections::HashMap,
+    env, fs,
+    hint::black_box,
+    io::{self, Read},
+    panic::{self, AssertUnwindSafe},
+    process::Command,
+    result,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread, time,
+};
+
+fn print_time(d: time::Duration, allocs: Option<&alloc::Stats>) {
+    match allocs {
+        Some(allocs) => println!("> {} seconds, {}", report::format_time(d), allocs),
+        None => println!("> {} seconds", report::format_time(d)),
+    }
+}
+
+/// Calls `f` with `a` and measures how long it took.
+pub fn timed<F: Fn(A) -> B, A, B>(f: F, a: A) -> (B, time::Duration) {
+    let now = time::Instant::now();
+    let res = f(a);
+    (res, now.elapsed())
+}
+
+/// Calls `f` with `a` and measures how long it took and, with the
+/// `count-alloc` feature, what it allocated.
+fn measured<F: Fn(A) -> B, A, B>(f: F, a: A) -> (B, time::Duration, Option<alloc::Stats>) {
+    if cfg!(feature = "count-alloc") {
+        let ((res, d), allocs) = alloc::measure(|| timed(f, a));
+        (res, d, Some(allocs))
+    } else {
+        let (res, d) = timed(f, a);
+        (res, d, None)
+    }
+}
+
+/// Options shared by the run, bench and verify modes
+#[derive(Debug)]
+pub struct Options {
+    /// Only run this part, rather than all of them
+    pub part: Option<usize>,
+    pub format: Format,
+    /// Number of benchmark runs, overriding `budget`
+    pub runs: Option<usize>,
+    /// Time to spend benchmarking each part
+    pub budget: time::Duration,
+    /// Number of untimed runs before benchmarking
+    pub warmup: usize,
+    /// File of expected answers to verify against
+    pub answers: String,
+    /// Number of days to run at the same time
+    pub jobs: usize,
+    /// File that the timings of each run are added to
+    pub history: String,
+    /// Label to record timings under, instead of the git commit
+    pub label: Option<String>,
+    /// Relative slowdown beyond which a step counts as regressed
+    pub threshold: f64,
+    /// Time a step may take before it is given up on
+    pub timeout: Option<time::Duration>,
+    /// How much the days trace to stderr, see [`crate::trace`]
+    pub verbosity: u8,
+    /// Seed for the days that make random choices, see [`crate::rng`]
+    pub seed: Option<u64>,
+    /// Number of times to solve a part when profiling, overriding `duration`
+    pub iterations: Option<usize>,
+    /// Time to spend solving a part when profiling
+    pub duration: time::Duration,
+    /// Values given for the days' parameters, in place of their defaults
+    pub params: Vec<(String, i64)>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            part: None,
+            format: Format::default(),
+            runs: None,
+            budget: time::Duration::from_secs(1),
+            warmup: 1,
+            answers: "answers".to_string(),
+            jobs: 1,
+            history: "history".to_string(),
+            label: None,
+            threshold: 0.1,
+            timeout: None,
+            verbosity: 0,
+            seed: None,
+            iterations: None,
+            duration: time::Duration::from_secs(10),
+            params: Vec::new(),
+        }
+    }
+}
+
+/// The result of a step, how long it took and what it allocated
+type Outcome<T> = (Result<T>, time::Duration, Option<alloc::Stats>);
+
+/// A step of a day that has completed
+enum Event {
+    Parsed(Outcome<()>),
+    Solved(usize, Outcome<Answer>),
+}
+
+impl Event {
+    /// Resolves the position of a parse error in `input`, which must be the
+    /// string that was parsed.
+    fn locate(self, day: u32, input: &str) -> Self {
+        match self {
+            Event::Parsed((res, d, allocs)) => {
+                Event::Parsed((res.map_err(|e| e.locate(day, input)), d, allocs))
+            }
+            Event::Solved(part, (res, d, allocs)) => {
+                Event::Solved(part, (res.map_err(|e| e.locate(day, input)), d, allocs))
+            }
+        }
+    }
+}
+
+/// Calls `f`, turning a panic into an [`Error::Panic`] so that a solution
+/// with a bug doesn't take the whole run down with it.
+fn caught<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| Err(Error::panic(payload)))
+}
+
+/// Parses `input` and solves `parts`, passing each step to `report` as it
+/// completes. If parsing fails, each part fails with the same error.
+fn steps(
+    solution: &Solution,
+    input: &str,
+    params: &Params,
+    parts: &[usize],
+    mut report: impl FnMut(Event),
+) {
+    let parse = |input| caught(|| (solution.parse)(input, params));
+    let (parsed, parse, allocs) = measured(parse, input);
+    let parsed = match parsed {
+        Ok(parsed) => {
+            report(Event::Parsed((Ok(()), parse, allocs)));
+            parsed
+        }
+        Err(e) => {
+            report(Event::Parsed((Err(e.clone()), parse, allocs)));
+            for &part in parts {
+                report(Event::Solved(
+                    part,
+                    (Err(e.clone()), time::Duration::ZERO, None),
+                ));
+            }
+            return;
+        }
+    };
+    for &part in parts {
+        report(Event::Solved(
+            part,
+            measured(|part| caught(|| parsed.solve(part)), part),
+        ));
+    }
+}
+
+/// Runs [`steps`] on a detached thread, which can be abandoned if it hangs.
+fn spawn_steps(
+    solution: &'static Solution,
+    input: Arc<str>,
+    params: Params,
+    parts: Vec<usize>,
+) -> mpsc::Receiver<Event> {
+    let (tx, rx) = mpsc::channel();
+    // as much stack as the main thread, for the solutions that recurse deeply
+    thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(move || {
+            // errors refer to this thread's copy of the input, which the
+            // caller can't locate them in
+            steps(solution, &input, &params, &parts, |event| {
+                let _ = tx.send(event.locate(solution.day, &input));
+            })
+        })
+        .expect("failed to spawn a worker thread");
+    rx
+}
+
+/// Like [`steps`], but gives up on a step that takes longer than `timeout`,
+/// reporting [`Error::Timeout`] for it.
+///
+/// The steps run on a worker thread. Threads can't be stopped, so when a part
+/// times out its worker is left running in the background and a new one
+/// parses the input again to solve the remaining parts.
+fn steps_with_timeout(
+    solution: &'static Solution,
+    input: &str,
+    params: &Params,
+    parts: &[usize],
+    timeout: time::Duration,
+    mut report: impl FnMut(Event),
+) {
+    let input: Arc<str> = input.into();
+    let mut events = spawn_steps(solution, input.clone(), params.clone(), parts.to_vec());
+    let mut parsed = false;
+    let mut next = 0;
+    while next < parts.len() {
+        let error = match events.recv_timeout(timeout) {
+            // workers after the first parse again, which isn't worth reporting
+            Ok(Event::Parsed(outcome)) if !parsed => {
+                parsed = true;
+                report(Event::Parsed(outcome));
+                continue;
+            }
+            Ok(Event::Parsed(_)) => continue,
+            Ok(event) => {
+                next += 1;
+                report(event);
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => Error::Timeout,
+            Err(RecvTimeoutError::Disconnected) => Error::Panic {
+                msg: "the worker thread died".to_string(),
+            },
+        };
+        if !parsed {
+            report(Event::Parsed((Err(error.clone()), timeout, None)));
+            for &part in &parts[next..] {
+                report(Event::Solved(
+                    part,
+                    (Err(error.clone()), time::Duration::ZERO, None),
+                ));
+            }
+            return;
+        }
+        report(Event::Solved(parts[next], (Err(error), timeout, None)));
+        next += 1;
+        if next < parts.len() {
+            let parts = parts[next..].to_vec();
+            events = spawn_steps(solution, input.clone(), params.clone(), parts);
+        }
+    }
+}
+
+/// Parses a day's input and solves the selected parts, passing each step to
+/// `report` as it completes.
+fn run_steps(
+    solution: &'static Solution,
+    input: &str,
+    options: &Options,
+    report: impl FnMut(Event),
+) {
+    let parts: Vec<usize> = solution.selected(options.part).collect();
+    let params = solution.params(&options.params);
+    match options.timeout {
+        Some(timeout) => steps_with_timeout(solution, input, &params, &parts, timeout, report),
+        None => steps(solution, input, &params, &parts, report),
+    }
+}
+
+/// Solves the selected parts of a single day, printing the time taken by
+/// each step, and returns a record of each part.
+///
+/// Parts that time out are reported as such without stopping the run, which
+/// then fails with [`Error::Timeout`].
+pub fn run_day(solution: &'static Solution, input: &str, options: &Options) -> Result<Vec<Record>> {
+    println!("Day {}: {}", solution.day, solution.name);
+    let mut records = Vec::new();
+    let mut parse = time::Duration::ZERO;
+    let mut error = None;
+    let mut timed_out = false;
+    run_steps(solution, input, options, |event| {
+        if error.is_some() {
+            return;
+        }
+        match event {
+            Event::Parsed((parsed, d, allocs)) => {
+                print_time(d, allocs.as_ref());
+                parse = d;
+                match parsed {
+                    Ok(()) => println!("Parsed input"),
+                    Err(e) => error = Some(e),
+                }
+            }
+            Event::Solved(part, (answer, solve, allocs)) => {
+                print_time(solve, allocs.as_ref());
+                match answer {
+                    Ok(answer) => {
+                        match &answer {
+                            Answer::Text(text) => println!("Part {}:\n{}", part, text),
+                            answer => println!("Part {}: {}", part, answer),
+                        }
+                        records.push(Record {
+                            day: solution.day,
+                            part,
+                            answer: Ok(answer),
+                            parse,
+                            solve,
+                            allocs,
+                        });
+                    }
+                    Err(Error::Timeout) => {
+                        println!("Part {}: {}", part, Error::Timeout);
+                        timed_out = true;
+                    }
+                    Err(e) => error = Some(e),
+                }
+            }
+        }
+    });
+    match error {
+        Some(e) => Err(e.locate(solution.day, input)),
+        None if timed_out => Err(Error::Timeout),
+        None => Ok(records),
+    }
+}
+
+/// Calls `f` on each of `items` from up to `jobs` threads, and passes each
+/// item and its result to `report` in the order of `items`, as soon as they
+/// are ready.
+///
+/// There are never more threads than CPUs, so that items don't have to wait
+/// for each other and their timings stay meaningful.
+fn in_order<T: Sync, R: Send>(
+    items: &[T],
+    jobs: usize,
+    f: impl Fn(&T) -> R + Sync,
+    mut report: impl FnMut(&T, R),
+) {
+    let cpus = thread::available_parallelism().map_or(1, usize::from);
+    let jobs = jobs.min(cpus).min(items.len());
+    let (next, f) = (&AtomicUsize::new(0), &f);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            // as much stack as the main thread, like the workers of `spawn_steps`
+            thread::Builder::new()
+                .stack_size(8 << 20)
+                .spawn_scoped(scope, move || {
+                    let mut idx = next.fetch_add(1, Ordering::Relaxed);
+                    while let Some(item) = items.get(idx) {
+                        if tx.send((idx, f(item))).is_err() {
+                            break;
+                        }
+                        idx = next.fetch_add(1, Ordering::Relaxed);
+                    }
+                })
+                .expect("failed to spawn a worker thread");
+        }
+        drop(tx);
+
+        let mut ready = HashMap::new();
+        let mut reported = 0;
+        for (idx, res) in rx {
+            ready.insert(idx, res);
+            while let Some(res) = ready.remove(&reported) {
+                report(&items[reported], res);
+                reported += 1;
+            }
+        }
+    });
+}
+
+/// Parses a day's input and solves the selected parts, timing each step.
+///
+/// Returns the time taken to parse and what it allocated, along with a
+/// record per part.
+fn solve_day(
+    solution: &'static Solution,
+    input: &str,
+    options: &Options,
+) -> (time::Duration, Option<alloc::Stats>, Vec<Record>) {
+    let (mut parse, mut parse_allocs) = (time::Duration::ZERO, None);
+    let mut records = Vec::new();
+    run_steps(solution, input, options, |event| match event {
+        Event::Parsed((_, d, allocs)) => (parse, parse_allocs) = (d, allocs),
+        Event::Solved(part, (answer, solve, allocs)) => records.push(Record {
+            day: solution.day,
+            part,
+            answer: answer.map_err(|e| e.locate(solution.day, input).to_string()),
+            parse,
+            solve,
+            allocs,
+        }),
+    });
+    (parse, parse_allocs, records)
+}
+
+/// Runs the selected parts of each day against its input, reports the
+/// results in the requested format and returns them, including the parts
+/// that failed or timed out.
+///
+/// Up to `options.jobs` days run at the same time, but the results are still
+/// reported in day order.
+pub fn run_days(days: &[(&'static Solution, String)], options: &Options) -> Vec<Record> {
+    match options.format {
+        Format::Text => println!("{}", report::table_header()),
+        Format::Csv => println!("{}", report::csv_header()),
+        Format::Json => (),
+    }
+
+    let mut records = Vec::new();
+    let mut total = time::Duration::ZERO;
+    let solve =
+        |(solution, input): &(&'static Solution, String)| solve_day(solution, input, options);
+    in_order(
+        days,
+        options.jobs,
+        solve,
+        |(solution, _), (parse, allocs, day)| {
+            total += parse;
+            if options.format == Format::Text {
+                let row = report::table_parse_row(solution.day, parse, allocs.as_ref());
+                println!("{}", row);
+            }
+
+            for record in day {
+                total += record.solve;
+                match options.format {
+                    Format::Text => println!("{}", report::table_row(&record)),
+                    Format::Csv => println!("{}", report::csv_row(&record)),
+                    Format::Json => (),
+                }
+                records.push(record);
+            }
+        },
+    );
+
+    match options.format {
+        Format::Text => println!("{}", report::table_total(total)),
+        Format::Csv => (),
+        Format::Json => println!("{}", report::json(&records)),
+    }
+    records
+}
+
+/// Times `f` repeatedly, for the number of runs or the time budget given by
+/// `options`.
+pub fn bench_runs<F: Fn() -> Result<()>>(f: F, options: &Options) -> Result<bench::Stats> {
+    for _ in 0..options.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::new();
+    let start = time::Instant::now();
+    while match options.runs {
+        Some(runs) => samples.len() < runs,
+        None => samples.is_empty() || start.elapsed() < options.budget,
+    } {
+        let (res, d) = timed(|_| f(), ());
+        res?;
+        samples.push(d);
+    }
+    Ok(bench::Stats::new(&samples).unwrap())
+}
+
+/// Times parsing and the selected parts of each day repeatedly and reports
+/// statistics on the timings. A step that fails or panics is reported as an
+/// error, and the run moves on.
+pub fn bench_days(days: &[(&Solution, String)], options: &Options) {
+    println!("{}", bench::header());
+    for (solution, input) in days {
+        let params = solution.params(&options.params);
+        let error = |label: &str, e: Error| {
+            let e = e.locate(solution.day, input);
+            println!("{:>3}  {:>5}  error: {}", solution.day, label, e);
+        };
+        let parse = || caught(|| (solution.parse)(input, &params));
+        match bench_runs(|| parse().map(|_| ()), options) {
+            Ok(stats) => println!("{}", bench::row(solution.day, "parse", &stats)),
+            Err(e) => {
+                error("parse", e);
+                continue;
+            }
+        }
+        let parsed = match parse() {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                error("parse", e);
+                continue;
+            }
+        };
+
+        for part in solution.selected(options.part) {
+            let label = part.to_string();
+            match bench_runs(|| caught(|| parsed.solve(part)).map(|_| ()), options) {
+                Ok(stats) => println!("{}", bench::row(solution.day, &label, &stats)),
+                Err(e) => error(&label, e),
+            }
+        }
+    }
+}
+
+/// Parses a day's input once and solves `part` over and over, for an external
+/// profiler to sample: for `options.iterations` times if given, or else for
+/// `options.duration`.
+pub fn profile_day(solution: &Solution, input: &str, part: usize, options: &Options) -> Result<()> {
+    let params = solution.params(&options.params);
+    let parsed = (solution.parse)(input, &params).map_err(|e| e.locate(solution.day, input))?;
+    eprintln!(
+        "Profiling day {} part {} in process {}",
+        solution.day,
+        part,
+        std::process::id()
+    );
+
+    let mut runs = 0;
+    let start = time::Instant::now();
+    while match options.iterations {
+        Some(iterations) => runs < iterations,
+        None => runs == 0 || start.elapsed() < options.duration,
+    } {
+        if let Err(e) = black_box(parsed.solve(black_box(part))) {
+            return Err(e.locate(solution.day, input));
+        }
+        runs += 1;
+    }
+    let elapsed = start.elapsed();
+    eprintln!(
+        "{} iterations in {} seconds, {} seconds each",
+        runs,
+        report::format_time(elapsed),
+        report::format_time(elapsed.div_f64(runs as f64))
+    );
+    Ok(())
+}
+
+/// Checks the selected parts of each day against the expected answers and
+/// returns the number of parts that failed.
+pub fn verify_days(
+    days: &[(&'static Solution, String)],
+    answers: &Answers,
+    options: &Options,
+) -> usize {
+    println!("{:>3}  {:>4}  {:<7}  Answer", "Day", "Part", "Status");
+    let mut failed = 0;
+    let solve =
+        |(solution, input): &(&'static Solution, String)| solve_day(solution, input, options).2;
+    in_order(days, options.jobs, solve, |_, records| {
+        for record in records {
+            let expected = answers.get(record.day, record.part);
+            let status = Status::new(&record.answer, expected);
+            let detail = match (&record.answer, expected) {
+                (Err(e), _) => format!("error: {}", e),
+                (Ok(answer), Some(expected)) if status == Status::Fail => {
+                    format!("{} (expected {})", answer, expected)
+                }
+                (Ok(answer), _) => answer.to_string(),
+            };
+            if status == Status::Fail {
+                failed += 1;
+            }
+            println!(
+                "{:>3}  {:>4}  {:<7}  {}",
+                record.day, record.part, status, detail
+            );
+        }
+    });
+    failed
+}
+
+/// Solves mutated versions of each day's input, reporting those that made
+/// the solution panic.
+///
+/// Each mutated input is solved by a child process running the `fuzz-case`
+/// command of this binary, see [`fuzz_case`], which is killed if it takes
+/// longer than `options.timeout`, or 1 second without one. That way a
+/// solution that loops forever doesn't slow down the runs after it, and one
+/// that overflows its stack is reported rather than aborting the fuzzing.
+///
+/// Runs default to 100 per day. Inputs that time out are left unchecked, so a
+/// day stops as soon as most of its runs timed out. Fails with
+/// [`Error::Panics`] if any input caused a panic, and otherwise with
+/// [`Error::Unchecked`] if any day stopped.
+pub fn fuzz_days(days: &[(&'static Solution, String)], options: &Options) -> Result<()> {
+    let exe = env::current_exe()
+        .map_err(|e| Error::invalid(format!("Could not find this binary: {}", e)))?;
+    // the same seed for every child, which is printed once if it was drawn
+    let mut args = vec!["--seed".to_string(), rng::seed().to_string()];
+    if let Some(part) = options.part {
+        args.extend(["--part".to_string(), part.to_string()]);
+    }
+    for (name, value) in &options.params {
+        args.extend(["--param".to_string(), format!("{}={}", name, value)]);
+    }
+
+    let runs = options.runs.unwrap_or(100);
+    let timeout = options.timeout.unwrap_or(time::Duration::from_secs(1));
+    println!(
+        "{:>3}  {:>5}  {:>9}  {:>6}",
+        "Day", "Runs", "Unchecked", "Panics"
+    );
+    let (mut panics, mut unchecked) = (0, 0);
+    for (solution, input) in days {
+        let (mut done, mut timeouts, mut messages) = (0, 0, Vec::new());
+        while done < runs && timeouts <= runs / 2 {
+            let mutated = fuzz::input(solution.day, done, input);
+            let mut command = Command::new(&exe);
+            command
+                .args(["fuzz-case", &solution.day.to_string()])
+                .args(&args);
+            match fuzz::check_in_child(command, &mutated, timeout) {
+                fuzz::Outcome::Handled => (),
+                fuzz::Outcome::Timeout => timeouts += 1,
+                fuzz::Outcome::Panic(msg) => messages.push((done, msg)),
+            }
+            done += 1;
+        }
+        println!(
+            "{:>3}  {:>5}  {:>9}  {:>6}",
+            solution.day,
+            done,
+            timeouts,
+            messages.len()
+        );
+        for (run, msg) in &messages {
+            println!("     run {}: {}", run, msg);
+        }
+        if timeouts > runs / 2 {
+            println!("     stopped, most runs timed out");
+            unchecked += 1;
+        }
+        panics += messages.len();
+    }
+    if panics > 0 {
+        Err(Error::Panics { panics })
+    } else if unchecked > 0 {
+        Err(Error::Unchecked { days: unchecked })
+    } else {
+        Ok(())
+    }
+}
+
+/// Solves the mutated input of [`fuzz_days`] on stdin, as a child process of
+/// the fuzzing. Prints what happened to stdout and fails if a step panicked.
+pub fn fuzz_case(solution: &'static Solution, options: &Options) -> Result<()> {
+    let mut input = String::new();
+    // mutations can leave the input empty or invalid, which the solution
+    // should handle like any other
+    if io::stdin().read_to_string(&mut input).is_err() {
+        return Ok(());
+    }
+    let parts = solution.selected(options.part).collect();
+    let params = solution.params(&options.params);
+    fuzz::run(solution, input, params, parts).inspect_err(|e| println!("{}", e))
+}
+
+/// Accounts in a directory of inputs, one subdirectory each, sorted by name
+fn accounts(dir: &str) -> io::Result<Vec<String>> {
+    let mut accounts = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            accounts.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    accounts.sort();
+    Ok(accounts)
+}
+
+/// How a day went for one account: the time taken to parse and the outcome
+/// of each part, or `None` if the account has no input for the day
+type Cell = Option<(time::Duration, Vec<(usize, Result<Answer>, time::Duration)>)>;
+
+fn matrix_cell(answer: &Result<Answer>, solve: time::Duration) -> String {
+    let answer = match answer {
+        Ok(Answer::Text(text)) => format!("({} lines)", text.lines().count()),
+        Ok(answer) => answer.to_string(),
+        Err(Error::Timeout) => "TIMEOUT".to_string(),
+        Err(Error::Panic { .. }) => "PANIC".to_string(),
+        Err(_) => "ERROR".to_string(),
+    };
+    format!("{:<16}  {}", answer, report::format_time(solve))
+}
+
+/// Solves the selected parts of each day for every account in `dir`, which
+/// has a directory of inputs per account, e.g. `inputs/<account>/dayNN`, and
+/// prints the answers and timings as a matrix with a column per account.
+///
+/// Parts that fail are marked as `ERROR`, `PANIC` or `TIMEOUT` in the matrix
+/// and explained below it. Returns the number of failed parts.
+pub fn run_matrix(
+    days: &[&'static Solution],
+    dir: &str,
+    options: &Options,
+) -> result::Result<usize, String> {
+    let accounts = accounts(dir).map_err(|e| format!("Could not read '{}': {}", dir, e))?;
+    if accounts.is_empty() {
+        return Err(format!("No account directories in '{}'", dir));
+    }
+
+    let items: Vec<(&'static Solution, Option<String>)> = days
+        .iter()
+        .flat_map(|&solution| {
+            accounts.iter().map(move |account| {
+                let path = format!("{}/{}/day{:02}", dir, account, solution.day);
+                let input = read_input(solution.day, Some(&path)).ok();
+                (solution, input.map(|(input, _)| input))
+            })
+        })
+        .collect();
+    let solve = |(solution, input): &(&'static Solution, Option<String>)| -> Cell {
+        let input = input.as_ref()?;
+        let mut parse = time::Duration::ZERO;
+        let mut parts = Vec::new();
+        run_steps(solution, input, options, |event| match event {
+            Event::Parsed((_, d, _)) => parse = d,
+            Event::Solved(part, (answer, solve, _)) => {
+                let answer = answer.map_err(|e| e.locate(solution.day, input));
+                parts.push((part, answer, solve));
+            }
+        });
+        Some((parse, parts))
+    };
+    let mut cells = Vec::new();
+    in_order(&items, options.jobs, solve, |_, cell| cells.push(cell));
+
+    let width = accounts.iter().map(String::len).max().unwrap_or(0).max(31);
+    let mut row = format!("{:>3}  {:>5}", "Day", "Part");
+    for account in &accounts {
+        row += &format!("  {:<width$}", account);
+    }
+    println!("{}", row.trim_end());
+
+    let mut failures = Vec::new();
+    for (solution, cells) in days.iter().zip(cells.chunks(accounts.len())) {
+        let mut row = format!("{:>3}  {:>5}", solution.day, "parse");
+        for cell in cells {
+            let parse = match cell {
+                Some((parse, _)) => report::format_time(*parse),
+                None => "-".to_string(),
+            };
+            row += &format!("  {:<width$}", parse);
+        }
+        println!("{}", row.trim_end());
+
+        for part in solution.selected(options.part) {
+            let mut row = format!("{:>3}  {:>5}", solution.day, part);
+            for (account, cell) in accounts.iter().zip(cells) {
+                let outcome = cell
+                    .as_ref()
+                    .and_then(|(_, parts)| parts.iter().find(|(p, _, _)| *p == part));
+                let text = match outcome {
+                    Some((_, answer, solve)) => {
+                        if let Err(e) = answer {
+                            failures.push((solution.day, part, account, e));
+                        }
+                        matrix_cell(answer, *solve)
+                    }
+                    None => "-".to_string(),
+                };
+                row += &format!("  {:<width$}", text);
+            }
+            println!("{}", row.trim_end());
+        }
+    }
+
+    if !failures.is_empty() {
+        println!();
+    }
+    for (day, part, account, e) in &failures {
+        println!("Day {} part {}, {}: {}", day, part, account, e);
+    }
+    Ok(failures.len())
+}
+
+/// The input file and the examples of a day, which `watch_day` re-runs it on
+fn watched_files(day: u32, input: &str) -> Vec<String> {
+    let mut examples: Vec<String> = fs::read_dir(format!("examples/day{:02}", day))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    examples.sort();
+    examples.insert(0, input.to_string());
+    examples
+}
+
+fn modified(path: &str) -> Option<time::SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+/// Solves the selected parts of a day for each of `files`, showing how the
+/// answers changed since the `previous` run.
+fn watch_run(
+    solution: &'static Solution,
+    files: &[String],
+    previous: &mut HashMap<(String, usize), result::Result<Answer, String>>,
+    options: &Options,
+) {
+    let width = files.iter().map(String::len).max().unwrap_or(0);
+    println!("{:<width$}  {:>4}  {:<32}  Time", "File", "Part", "Answer");
+    for file in files {
+        let records = match read_input(solution.day, Some(file)) {
+            Ok((input, _)) => solve_day(solution, &input, options).2,
+            Err(msg) => {
+                println!("{:<width$}  {:>4}  error: {}", file, "", msg);
+                continue;
+            }
+        };
+        for record in records {
+            let mut answer = match &record.answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => format!("error: {}", e),
+            };
+            let key = (file.clone(), record.part);
+            match previous.insert(key, record.answer.clone()) {
+                Some(Ok(was)) if record.answer.as_ref() != Ok(&was) => {
+                    answer += &format!(" (was {})", was)
+                }
+                Some(Err(_)) if record.answer.is_ok() => answer += " (was an error)",
+                _ => (),
+            }
+            println!(
+                "{:<width$}  {:>4}  {:<32}  {}",
+                file,
+                record.part,
+                answer,
+                report::format_time(record.solve)
+            );
+        }
+    }
+}
+
+/// Re-runs the selected parts of a day whenever its input or one of its
+/// examples changes, until the process is stopped.
+///
+/// Files are polled for changes to their modification time, and examples
+/// that are added while watching are picked up too.
+pub fn watch_day(solution: &'static Solution, input: &str, options: &Options) -> ! {
+    const POLL: time::Duration = time::Duration::from_millis(500);
+    println!("Day {}: {}", solution.day, solution.name);
+    let mut seen = HashMap::new();
+    let mut previous = HashMap::new();
+    loop {
+        let files = watched_files(solution.day, input);
+        let times: HashMap<String, Option<time::SystemTime>> = files
+            .iter()
+            .map(|file| (file.clone(), modified(file)))
+            .collect();
+        if times != seen {
+            watch_run(solution, &files, &mut previous, options);
+            println!("Watching {} file(s) for changes", files.len());
+            println!();
+            seen = times;
+        }
+        thread::sleep(POLL);
+    }
+}
+
+/// Reads stdin and normalises it, failing if there is nothing on it.
+fn read_stdin() -> result::Result<String, String> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("Could not read stdin: {}", e))?;
+    let input = input::normalize(&input);
+    if input.trim().is_empty() {
+        return Err("Empty input on stdin".to_string());
+    }
+    Ok(input)
+}
+
+/// Whether stdin is a pipe or a regular file, rather than a terminal or a
+/// device such as `/dev/null`
+#[cfg(unix)]
+fn stdin_piped() -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    fs::metadata("/dev/stdin").is_ok_and(|meta| meta.is_file() || meta.file_type().is_fifo())
+}
+
+#[cfg(not(unix))]
+fn stdin_piped() -> bool {
+    use std::io::IsTerminal;
+    !io::stdin().is_terminal()
+}
+
+/// Where the input of a day was read from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Source {
+    /// The day's default input file, `input/dayNN`
+    Default,
+    /// A file given on the command line
+    File,
+    Stdin,
+}
+
+/// Reads the input of a single day from `path`, where `-` means stdin, and
+/// normalises it.
+///
+/// Without a path, input is read from stdin if it is piped in or redirected
+/// from a file, and from the day's default input otherwise, as when stdin is
+/// a terminal or `/dev/null`. Empty input on stdin is an error.
+pub fn read_input(day: u32, path: Option<&str>) -> result::Result<(String, Source), String> {
+    let (path, source) = match path {
+        Some("-") => return Ok((read_stdin()?, Source::Stdin)),
+        Some(path) => (path.to_string(), Source::File),
+        None if stdin_piped() => return Ok((read_stdin()?, Source::Stdin)),
+        None => (input_path(day), Source::Default),
+    };
+    fs::read_to_string(&path)
+        .map(|input| (input::normalize(&input), source))
+        .map_err(|_| format!("No such file: '{}'", path))
+}
+
+/// Selected days, each with its input
+pub type Inputs = Vec<(&'static Solution, String)>;
+
+/// Reads the inputs for the selected days, and tells where they came from.
+///
+/// A single day may be given an input file or read from stdin, and fails if
+/// its input can't be read. Days of a larger selection use their default
+/// input and are skipped if it is missing.
+pub fn read_inputs(
+    days: Vec<&'static Solution>,
+    input: Option<String>,
+    options: &Options,
+) -> result::Result<(Inputs, Source), String> {
+    if let [solution] = days[..] {
+        if options.part.is_some_and(|part| part > solution.parts) {
+            return Err(format!(
+                "Day {} has only {} part(s)",
+                solution.day, solution.parts
+            ));
+        }
+
+        let (input, source) = read_input(solution.day, input.as_deref())?;
+        Ok((vec![(solution, input)], source))
+    } else if input.is_some() {
+        Err("An input file can only be given for a single day".to_string())
+    } else {
+        let days = days
+            .into_iter()
+            .filter_map(|solution| {
+                let path = input_path(solution.day);
+                if let Ok(i) = fs::read_to_string(&path) {
+                    Some((solution, input::normalize(&i)))
+                } else {
+                    eprintln!("Skipping day {}, no such file: '{}'", solution.day, path);
+                    None
+                }
+            })
+            .collect();
+        Ok((days, Source::Default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn in_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let mut reported = Vec::new();
+        super::in_order(
+            &items,
+            4,
+            |&n| n * n,
+            |&n, square| reported.push((n, square)),
+        );
+        let expected: Vec<_> = items.iter().map(|&n| (n, n * n)).collect();
+        assert_eq!(reported, expected);
+    }
+
+    #[test]
+    fn caught() {
+        assert_eq!(super::caught(|| Ok(7)), Ok(7));
+        let panicked = super::caught::<()>(|| panic!("boom"));
+        assert_eq!(panicked.unwrap_err().to_string(), "panicked: boom");
+    }
+
+    #[test]
+    fn timeout_locates_errors() {
+        let solution = crate::solution(2).unwrap();
+        let input = "Game 1: 3 blue\nGame 2: 1 bleu";
+        let mut errors = Vec::new();
+        let timeout = std::time::Duration::from_secs(5);
+        let params = solution.params(&[]);
+        super::steps_with_timeout(solution, input, &params, &[1], timeout, |event| {
+            if let super::Event::Parsed((Err(e), _, _)) = event {
+                errors.push(e.locate(2, input).to_string());
+            }
+        });
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("line 2, column 11"), "{}", errors[0]);
+    }
+}
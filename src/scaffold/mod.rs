@@ -0,0 +1,212 @@
+use crate::{input_path, Error, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Source of a new day's module, which parses its input into lines and has
+/// a stub for each part
+fn template(day: u32, name: &str) -> String {
+    format!(
+        r#"pub fn parse(input: &str) -> crate::Result<Vec<&str>> {{
+    Ok(input.lines().collect())
+}}
+
+pub fn part1(_lines: &[&str]) -> crate::Result<usize> {{
+    Err(crate::Error::invalid("part 1 is not solved yet"))
+}}
+
+pub fn part2(_lines: &[&str]) -> crate::Result<usize> {{
+    Err(crate::Error::invalid("part 2 is not solved yet"))
+}}
+
+pub const SOLUTION: crate::Solution = solution!({}, {:?}, parse, [part1, part2]);
+"#,
+        day, name
+    )
+}
+
+/// Inserts `line` among the lines of `lines[range]` that start with
+/// `prefix`, keeping them sorted, or at the end of the range if there are
+/// none.
+fn insert_sorted(
+    lines: &mut Vec<String>,
+    range: std::ops::Range<usize>,
+    prefix: &str,
+    line: String,
+) {
+    let idx = range
+        .clone()
+        .filter(|&idx| lines[idx].starts_with(prefix))
+        .find(|&idx| lines[idx] > line)
+        .or_else(|| {
+            range
+                .clone()
+                .rev()
+                .find(|&idx| lines[idx].starts_with(prefix))
+                .map(|idx| idx + 1)
+        })
+        .unwrap_or(range.end);
+    lines.insert(idx, line);
+}
+
+/// Declares the module of `day` in the source of `lib.rs` and adds its
+/// solution to `SOLUTIONS`.
+fn register(lib: &str, day: u32) -> Result<String> {
+    let module = format!("day{:02}", day);
+    let mut lines: Vec<String> = lib.lines().map(String::from).collect();
+    let declared = format!("pub mod {};", module);
+    if lines.contains(&declared) {
+        return Err(Error::invalid(format!("{} is already declared", module)));
+    }
+
+    let solutions = lines
+        .iter()
+        .position(|line| line.starts_with("pub const SOLUTIONS: [Solution; "))
+        .ok_or_else(|| Error::invalid("SOLUTIONS not found in lib.rs"))?;
+    let end = solutions
+        + lines[solutions..]
+            .iter()
+            .position(|line| line == "];")
+            .ok_or_else(|| Error::invalid("end of SOLUTIONS not found in lib.rs"))?;
+    let len = (solutions + 1..end)
+        .filter(|&idx| lines[idx].starts_with("    day"))
+        .count();
+    lines[solutions] = format!("pub const SOLUTIONS: [Solution; {}] = [", len + 1);
+    let solution = format!("    {}::SOLUTION,", module);
+    insert_sorted(&mut lines, solutions + 1..end, "    day", solution);
+
+    // modules are declared in alphabetical order
+    insert_sorted(&mut lines, 0..solutions, "pub mod ", declared);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Creates a file with `contents`, unless it already exists, and returns
+/// whether it did.
+fn create(path: &Path, contents: &str) -> Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| Error::invalid(format!("Could not create '{}': {}", dir.display(), e)))?;
+    }
+    fs::write(path, contents)
+        .map_err(|e| Error::invalid(format!("Could not write '{}': {}", path.display(), e)))?;
+    Ok(true)
+}
+
+/// Sets up a new day in the repository at the current directory: a module
+/// with stubs for its parts, registered in `src/lib.rs`, and empty input and
+/// example files. Returns the files that were created or changed.
+///
+/// The day's input stays empty until it is solved, which tells the tests
+/// not to expect answers for it yet.
+pub fn new_day(day: u32, name: &str) -> Result<Vec<String>> {
+    new_day_in(Path::new("."), day, name)
+}
+
+/// Like [`new_day`], for the repository at `root`.
+fn new_day_in(root: &Path, day: u32, name: &str) -> Result<Vec<String>> {
+    let module = format!("src/day{:02}/mod.rs", day);
+    if root.join(&module).exists() {
+        return Err(Error::invalid(format!("'{}' already exists", module)));
+    }
+    let lib_path: PathBuf = root.join("src/lib.rs");
+    let lib = fs::read_to_string(&lib_path)
+        .map_err(|e| Error::invalid(format!("Could not read 'src/lib.rs': {}", e)))?;
+    let lib = register(&lib, day)?;
+
+    let examples = format!("examples/day{:02}", day);
+    let files = [
+        (module, template(day, name)),
+        (input_path(day), String::new()),
+        (format!("{}/ex1.txt", examples), String::new()),
+        (
+            format!("{}/answers", examples),
+            "# <example> <part> <answer>\n".to_string(),
+        ),
+    ];
+    let mut created = Vec::new();
+    for (path, contents) in files {
+        if create(&root.join(&path), &contents)? {
+            created.push(path);
+        }
+    }
+    fs::write(&lib_path, lib)
+        .map_err(|e| Error::invalid(format!("Could not write 'src/lib.rs': {}", e)))?;
+    created.push("src/lib.rs".to_string());
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process};
+
+    #[test]
+    fn register() {
+        let lib = "pub mod bench;
+pub mod day01;
+pub mod day03;
+pub mod error;
+
+pub const SOLUTIONS: [Solution; 2] = [
+    day01::SOLUTION,
+    day03::SOLUTION,
+];
+";
+        assert_eq!(
+            super::register(lib, 2).unwrap(),
+            "pub mod bench;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod error;
+
+pub const SOLUTIONS: [Solution; 3] = [
+    day01::SOLUTION,
+    day02::SOLUTION,
+    day03::SOLUTION,
+];
+"
+        );
+        assert!(super::register(lib, 3).is_err());
+
+        let empty =
+            "pub mod bench;\npub mod error;\n\npub const SOLUTIONS: [Solution; 0] = [\n];\n";
+        assert_eq!(
+            super::register(empty, 1).unwrap(),
+            "pub mod bench;\npub mod day01;\npub mod error;\n\npub const SOLUTIONS: [Solution; 1] = [\n    day01::SOLUTION,\n];\n"
+        );
+    }
+
+    /// A new day has no answers yet, which mustn't fail the registry test.
+    #[test]
+    fn new_day_in() {
+        let root = std::env::temp_dir().join(format!("aoc2023-scaffold-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (path, contents) in [
+            ("src/lib.rs", "pub mod day01;\n\npub const SOLUTIONS: [Solution; 1] = [\n    day01::SOLUTION,\n];\n"),
+            ("answers", "1 1 5\n1 2 6\n"),
+            ("input/day01", "x\n"),
+        ] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::write(root.join(path), contents).unwrap();
+        }
+
+        let created = super::new_day_in(&root, 2, "Day 2").unwrap();
+        assert!(created.contains(&"src/day02/mod.rs".to_string()));
+        assert!(super::new_day_in(&root, 2, "Day 2").is_err());
+
+        let lib = fs::read_to_string(root.join("src/lib.rs")).unwrap();
+        let days: Vec<(u32, usize)> = lib
+            .lines()
+            .filter_map(|line| line.strip_prefix("    day")?.strip_suffix("::SOLUTION,"))
+            .map(|day| (day.parse().unwrap(), 2))
+            .collect();
+        assert_eq!(days, [(1, 2), (2, 2)]);
+        assert_eq!(crate::tests::missing_answers(&root, &days), []);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
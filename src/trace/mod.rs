@@ -0,0 +1,28 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much the days trace, as set by `-v` (1) and `-vv` (2)
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Sets how much the days trace, from 0 for nothing.
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+/// Whether messages of `level` are traced
+pub fn enabled(level: u8) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level
+}
+
+/// Name of the day a module path such as `aoc2023::day08` belongs to
+pub fn source(module: &str) -> &str {
+    module.rsplit("::").next().unwrap_or(module)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn source() {
+        assert_eq!(super::source("aoc2023::day08"), "day08");
+        assert_eq!(super::source("day08"), "day08");
+    }
+}